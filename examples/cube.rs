@@ -25,7 +25,7 @@ fn setup(
     // Generate mesh
     let mut mesh = RoundedBox {
         size: Vec3::new(2., 2., 2.),
-        radius: 0.4,
+        radius: Vec3::splat(0.4),
     }
     .mesh()
     .with_face()
@@ -0,0 +1,395 @@
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+    },
+};
+
+use crate::RoundedBoxMeshOptions;
+#[cfg(feature = "uvf")]
+use crate::ATTRIBUTE_FACE;
+
+/// A ring of vertices around the outline of an [`Extrusion`].
+#[derive(Copy, Clone)]
+pub(crate) struct Ring {
+    /// The distance outwards from the outline of the inset shape.
+    pub offset: f32,
+    pub z: f32,
+    /// The normal in the outward and upward directions.
+    pub normal: Vec2,
+    /// The top or bottom face, or none for the sides.
+    pub face: Option<u32>,
+    /// The distance along the surface from the top of the sides, or for the top and bottom, from
+    /// the middle of the edge towards the sides.
+    #[cfg_attr(not(feature = "uvf"), allow(dead_code))]
+    pub length: f32,
+}
+
+/// A column of vertices from the top to the bottom of an [`Extrusion`].
+#[derive(Copy, Clone)]
+pub(crate) struct Column {
+    /// The point on the outline of the inset shape.
+    pub corner: Vec2,
+    /// The outward direction from the corner.
+    pub dir: Vec2,
+    pub face: u32,
+    /// The distance along the outline from the start of the side, as a fraction of the side.
+    #[cfg_attr(not(feature = "uvf"), allow(dead_code))]
+    pub u: f32,
+}
+
+/// How the top and bottom of an [`Extrusion`] are divided into triangles.
+pub(crate) enum Caps {
+    /// Fans around a vertex at the centre, for outlines which are convex around the axis.
+    Fan,
+    /// Triangles between the columns, for any simple outline.
+    Triangulated,
+}
+
+/// A shape extruded along Z with rounded top and bottom edges, made of the vertices where its
+/// rings meet its columns.
+pub(crate) struct Extrusion {
+    /// The rings from the top to the bottom.
+    pub rings: Vec<Ring>,
+    /// The distance along the surface from the top to the bottom of the sides.
+    pub side_length: f32,
+    /// The columns anticlockwise around the outline.
+    pub columns: Vec<Column>,
+    pub caps: Caps,
+    /// Whether the sides curve all the way around, so that omitting a side omits it between
+    /// every pair of columns rather than only where it is flat.
+    pub curved_sides: bool,
+}
+
+/// Returns the rings around an edge of `radius` at each end of a core of half height
+/// `core_height`, from the top to the bottom, and the distance along the surface between the
+/// ends of the sides. The rings are duplicated where the edges are split between faces.
+pub(crate) fn edge_rings(
+    subdivisions: u32,
+    radius: f32,
+    core_height: f32,
+    faces: [u32; 2],
+    split_faces: bool,
+) -> (Vec<Ring>, f32) {
+    let stack_step = FRAC_PI_2 / subdivisions as f32;
+    let side_length = 2.0 * (core_height + FRAC_PI_4 * radius);
+    let mut rings = Vec::with_capacity(2 * subdivisions as usize + 4);
+    for (z_sign, face) in [(1.0, faces[0]), (-1.0, faces[1])] {
+        let half: Vec<Ring> = (0..=subdivisions)
+            .flat_map(|stack| {
+                let angle = stack as f32 * stack_step;
+                // Exactly vertical at the top of the edge, so that the top is flat
+                let normal = if stack == subdivisions {
+                    Vec2::Y
+                } else {
+                    Vec2::from_angle(angle)
+                };
+                let ring = |face| Ring {
+                    offset: radius * normal.x,
+                    z: z_sign * (core_height + radius * normal.y),
+                    normal: Vec2::new(normal.x, z_sign * normal.y),
+                    face,
+                    length: radius * (FRAC_PI_4 - angle),
+                };
+                let (side, cap) = (ring(None), ring(Some(face)));
+                if !split_faces {
+                    vec![cap]
+                } else if 2 * stack < subdivisions {
+                    vec![side]
+                } else if 2 * stack == subdivisions {
+                    vec![side, cap]
+                } else {
+                    vec![cap]
+                }
+            })
+            .collect();
+        if z_sign > 0.0 {
+            rings.extend(half.into_iter().rev());
+        } else {
+            rings.extend(half.into_iter().map(|ring| Ring {
+                length: if ring.face.is_none() {
+                    side_length - ring.length
+                } else {
+                    ring.length
+                },
+                ..ring
+            }));
+        }
+    }
+    (rings, side_length)
+}
+
+/// Returns the columns anticlockwise around a simple polygon, wound anticlockwise, whose corners
+/// are filleted with `corner_radius` and which is inset by `edge_radius`, starting from the first
+/// corner. The side which leaves a point of the polygon is numbered one more than the point.
+pub(crate) fn fillet_columns(
+    outline: &[Vec2],
+    corner_radius: f32,
+    edge_radius: f32,
+    subdivisions: u32,
+    split_faces: bool,
+) -> Vec<Column> {
+    let sides = outline.len() as u32;
+    let point = |i: u32| outline[(i % sides) as usize];
+    let side_normal = |i: u32| {
+        let dir = (point(i + 1) - point(i)).normalize();
+        Vec2::new(dir.y, -dir.x)
+    };
+    // The angle the outline turns through at each corner, positive where it is convex
+    let turns: Vec<f32> = (0..sides)
+        .map(|i| {
+            let (from, to) = (side_normal(i + sides - 1), side_normal(i));
+            from.perp_dot(to).atan2(from.dot(to))
+        })
+        .collect();
+    let turn = |i: u32| turns[(i % sides) as usize];
+    // The lengths of the sides, from the middle of one corner to the middle of the next
+    let side_widths: Vec<f32> = (0..sides)
+        .map(|i| {
+            let [start, end] = [i, i + 1].map(|corner| turn(corner).abs() / 2.0);
+            let straight =
+                point(i).distance(point(i + 1)) - corner_radius * (start.tan() + end.tan());
+            debug_assert!(straight >= -1e-5, "fillets overlap");
+            straight + corner_radius * (start + end)
+        })
+        .collect();
+
+    // Each corner is a fillet around a centre inside the polygon where it is convex and outside
+    // where it is concave, and is split between the sides which meet there
+    let mut columns = Vec::with_capacity((sides * (subdivisions + 2)) as usize);
+    for corner in 0..sides {
+        let (from, to) = (side_normal(corner + sides - 1), side_normal(corner));
+        let turn = turn(corner);
+        let sign = if turn < 0.0 { -1.0 } else { 1.0 };
+        let centre = point(corner) - sign * corner_radius * (from + to) / (1.0 + from.dot(to));
+        let inset_radius = sign * corner_radius - edge_radius;
+        let (end_side, start_side) = ((corner + sides - 1) % sides, corner);
+        let half = turn.abs() / 2.0;
+        for sector in 0..=subdivisions {
+            let fraction = sector as f32 / subdivisions as f32;
+            // Exactly the normals of the sides at the ends of the corner, so that the sides are
+            // flat
+            let dir = match sector {
+                0 => from,
+                _ if sector == subdivisions => to,
+                _ => Vec2::from_angle(fraction * turn).rotate(from),
+            };
+            let angle = fraction * turn.abs();
+            let column = |side: u32, length: f32| Column {
+                corner: centre + inset_radius * dir,
+                dir,
+                face: side + 1,
+                u: length / side_widths[side as usize],
+            };
+            let end = column(
+                end_side,
+                side_widths[end_side as usize] - corner_radius * (half - angle),
+            );
+            let start = column(start_side, corner_radius * (angle - half));
+            if 2 * sector < subdivisions {
+                columns.push(end);
+            } else if 2 * sector == subdivisions && split_faces {
+                columns.extend([end, start]);
+            } else {
+                columns.push(start);
+            }
+        }
+    }
+    columns
+}
+
+/// Divides a simple polygon, wound anticlockwise, into triangles by clipping ears, returning
+/// the indices of their points. Points which repeat the previous point are skipped.
+pub(crate) fn triangulate(points: &[Vec2]) -> Vec<[u32; 3]> {
+    let count = points.len();
+    let mut remaining: Vec<u32> = (0..count)
+        .filter(|&i| points[i] != points[(i + count - 1) % count])
+        .map(|i| i as u32)
+        .collect();
+    let mut triangles = Vec::with_capacity(remaining.len());
+    while remaining.len() > 3 {
+        let count = remaining.len();
+        let corners =
+            |i: usize| [(i + count - 1) % count, i, (i + 1) % count].map(|j| remaining[j]);
+        // An ear is a convex corner with no other point inside it or on its sides, or a straight
+        // one
+        let is_ear = |i: usize| {
+            let [a, b, c] = corners(i).map(|j| points[j as usize]);
+            let area = (b - a).perp_dot(c - b);
+            area == 0.0
+                || area > 0.0
+                    && remaining.iter().all(|&j| {
+                        let p = points[j as usize];
+                        p == a
+                            || p == b
+                            || p == c
+                            || [(a, b), (b, c), (c, a)]
+                                .iter()
+                                .any(|&(from, to)| (to - from).perp_dot(p - from) < 0.0)
+                    })
+        };
+        // Fall back to any corner if rounding hides every ear
+        let ear = (0..count).find(|&i| is_ear(i)).unwrap_or(0);
+        let [a, b, c] = corners(ear).map(|j| points[j as usize]);
+        if (b - a).perp_dot(c - b) != 0.0 {
+            triangles.push(corners(ear));
+        }
+        remaining.remove(ear);
+    }
+    if let [a, b, c] = remaining[..] {
+        triangles.push([a, b, c]);
+    }
+    triangles
+}
+
+impl Extrusion {
+    /// Builds the mesh. `cap_uv` maps a position on the top or bottom and its ring to texture
+    /// coordinates as seen from above, with V increasing along Y, and is mirrored for the top.
+    pub fn build(
+        &self,
+        options: &RoundedBoxMeshOptions,
+        cap_uv: impl Fn(Vec2, &Ring) -> Vec2,
+    ) -> Mesh {
+        let Extrusion {
+            rings,
+            side_length,
+            columns,
+            ..
+        } = self;
+        let fan = matches!(self.caps, Caps::Fan);
+        let (top, bottom) = (&rings[0], &rings[rings.len() - 1]);
+
+        // Generate vertices, starting with the centre of the top and ending with the centre of
+        // the bottom where the caps are fans
+        let outline = columns.len() as u32;
+        let centres = if fan { 1 } else { 0 };
+        let facing = if options.inverted { -1.0 } else { 1.0 };
+        let total_vertices = 2 * centres as usize + rings.len() * outline as usize;
+        let mut positions: Vec<[f32; 3]> = Vec::with_capacity(total_vertices);
+        let mut normals: Vec<[f32; 3]> = Vec::with_capacity(total_vertices);
+        #[cfg(feature = "uvf")]
+        let mut uvs: Vec<[f32; 2]> = Vec::new();
+        #[cfg(feature = "uvf")]
+        let mut faces: Vec<u32> = Vec::new();
+        let mut push_vertex = |pos: Vec3, normal: Vec3, column: &Column, ring: &Ring| {
+            positions.push(pos.to_array());
+            normals.push((facing * normal).to_array());
+            #[cfg(feature = "uvf")]
+            if options.is_generate_uv() {
+                let uv = match ring.face {
+                    None => Vec2::new(column.u, ring.length / side_length),
+                    Some(face) => {
+                        let uv = cap_uv(pos.xy(), ring);
+                        if Some(face) == top.face {
+                            Vec2::new(uv.x, 1.0 - uv.y)
+                        } else {
+                            uv
+                        }
+                    }
+                };
+                let uv = if options.inverted {
+                    Vec2::new(1.0 - uv.x, uv.y)
+                } else {
+                    uv
+                };
+                uvs.push(uv.to_array());
+            }
+            #[cfg(feature = "uvf")]
+            if options.is_generate_face() {
+                faces.push(ring.face.unwrap_or(column.face));
+            }
+            #[cfg(not(feature = "uvf"))]
+            let _ = (column, ring, &cap_uv, side_length);
+        };
+        if fan {
+            push_vertex(Vec3::Z * top.z, Vec3::Z, &columns[0], top);
+        }
+        for ring in rings {
+            for column in columns {
+                let pos = (column.corner + ring.offset * column.dir).extend(ring.z);
+                let normal = (ring.normal.x * column.dir).extend(ring.normal.y);
+                push_vertex(pos, normal, column, ring);
+            }
+        }
+        if fan {
+            push_vertex(Vec3::Z * bottom.z, Vec3::NEG_Z, &columns[0], bottom);
+        }
+
+        // Generate indices, excluding the degenerate triangles of sharp edges and corners
+        let mut indices: Vec<u32> = Vec::new();
+        let mut push_triangle = |face: Option<u32>, triangle: [u32; 3]| {
+            let [a, b, c] = triangle.map(|i| positions[i as usize]);
+            let omitted = face.is_some_and(|face| options.is_face_omitted(face));
+            if a != b && b != c && c != a && !omitted {
+                indices.extend(triangle);
+            }
+        };
+        let ring_index = |ring: u32, column: u32| centres + ring * outline + column % outline;
+        let last = rings.len() as u32 - 1;
+        if fan {
+            let centre = total_vertices as u32 - 1;
+            for column in 0..outline {
+                let next = column + 1;
+                push_triangle(top.face, [0, ring_index(0, column), ring_index(0, next)]);
+                push_triangle(
+                    bottom.face,
+                    [centre, ring_index(last, next), ring_index(last, column)],
+                );
+            }
+        } else {
+            let cap: Vec<Vec2> = columns.iter().map(|column| column.corner).collect();
+            for [a, b, c] in triangulate(&cap) {
+                push_triangle(top.face, [a, b, c].map(|column| ring_index(0, column)));
+                push_triangle(
+                    bottom.face,
+                    [a, c, b].map(|column| ring_index(last, column)),
+                );
+            }
+        }
+        for column in 0..outline {
+            let next = (column + 1) % outline;
+            // Only the sides between the edges are flat enough to omit, and only between the
+            // corners unless they curve all the way around
+            let flat_column =
+                self.curved_sides || columns[column as usize].dir == columns[next as usize].dir;
+            for ring in 0..last {
+                let face = (flat_column
+                    && rings[ring as usize].normal == Vec2::X
+                    && rings[ring as usize + 1].normal == Vec2::X)
+                    .then_some(columns[next as usize].face);
+                let jj = ring_index(ring, column);
+                let jk = ring_index(ring + 1, column);
+                let kj = ring_index(ring, next);
+                let kk = ring_index(ring + 1, next);
+                push_triangle(face, [jj, jk, kj]);
+                push_triangle(face, [kj, jk, kk]);
+            }
+        }
+        if options.is_reversed(options.inverted) {
+            for triangle in indices.chunks_exact_mut(3) {
+                triangle.swap(1, 2);
+            }
+        }
+
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        mesh.insert_indices(Indices::U32(indices));
+        debug_assert_eq!(positions.len(), total_vertices);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        #[cfg(feature = "uvf")]
+        if options.is_generate_uv() {
+            mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        }
+        #[cfg(feature = "uvf")]
+        if options.is_generate_face() {
+            mesh.insert_attribute(ATTRIBUTE_FACE, faces);
+        }
+        mesh
+    }
+}
@@ -0,0 +1,292 @@
+use bevy::{prelude::*, render::mesh::VertexAttributeValues};
+
+use crate::{rounded_frustum::build_frustum, RoundedBoxMeshOptions, RoundedFrustum};
+
+/// The shape of the dish in the top of a [`Keycap`].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum KeycapDish {
+    /// A cylindrical dish, which curves across X and runs straight along Y.
+    #[default]
+    Cylindrical,
+    /// A spherical dish, whose rim is the largest circle which fits in the top.
+    Spherical,
+}
+
+impl KeycapDish {
+    /// Returns the depth of a dish which is `depth` deep at its centre, in a top with the given
+    /// half size, and the gradient of the depth, at `pos`.
+    fn depth_at(self, depth: f32, half_size: Vec2, pos: Vec2) -> (f32, Vec2) {
+        let (rim, offset) = match self {
+            KeycapDish::Cylindrical => (half_size.x, Vec2::new(pos.x, 0.0)),
+            KeycapDish::Spherical => (half_size.min_element(), pos),
+        };
+        if depth <= 0.0 || offset.length_squared() >= rim * rim {
+            return (0.0, Vec2::ZERO);
+        }
+        // The radius of the cylinder or sphere which is as deep as the dish at its centre and
+        // meets the top at its rim
+        let radius = (rim * rim + depth * depth) / (2.0 * depth);
+        let root = (radius * radius - offset.length_squared()).sqrt();
+        (depth - radius + root, -offset / root)
+    }
+}
+
+/// A keycap for a mechanical keyboard, which is a [`RoundedFrustum`] with a dish in its top.
+///
+/// The faces and texture coordinates are laid out as for [`RoundedFrustum`], so the top, face 0,
+/// can carry a legend. The dish displaces the vertices of a frustum, so `segments` must be high
+/// enough for the top to follow the dish.
+#[derive(Copy, Clone, Debug)]
+pub struct Keycap {
+    /// The shape of the keycap without its dish.
+    pub frustum: RoundedFrustum,
+    /// The shape of the dish.
+    pub dish: KeycapDish,
+    /// The depth of the dish at its centre.
+    pub dish_depth: f32,
+}
+
+impl Keycap {
+    /// Creates a keycap.
+    pub const fn new(frustum: RoundedFrustum, dish: KeycapDish, dish_depth: f32) -> Self {
+        Self {
+            frustum,
+            dish,
+            dish_depth,
+        }
+    }
+}
+
+impl Default for Keycap {
+    fn default() -> Self {
+        Self::new(
+            RoundedFrustum::new(Vec2::ONE, Vec2::splat(0.7), 0.5, 0.05),
+            KeycapDish::Cylindrical,
+            0.03,
+        )
+    }
+}
+
+impl Meshable for Keycap {
+    type Output = KeycapMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        KeycapMeshBuilder {
+            keycap: *self,
+            subdivisions: 4,
+            segments: 8,
+            options: RoundedBoxMeshOptions::DEFAULT,
+        }
+    }
+}
+
+impl From<Keycap> for Mesh {
+    fn from(value: Keycap) -> Self {
+        value.mesh().build()
+    }
+}
+
+/// A builder used for creating a [`Mesh`] with a [`Keycap`] shape.
+#[derive(Copy, Clone, Debug)]
+pub struct KeycapMeshBuilder {
+    /// The [`Keycap`] shape.
+    pub keycap: Keycap,
+    /// The number of sectors and stacks in each corner.
+    pub subdivisions: usize,
+    /// The number of segments along each edge of the top and sides, which sets how finely the
+    /// dish is tessellated.
+    pub segments: usize,
+    /// Mesh generation options.
+    pub options: RoundedBoxMeshOptions,
+}
+
+impl Default for KeycapMeshBuilder {
+    fn default() -> Self {
+        Keycap::default().mesh()
+    }
+}
+
+impl MeshBuilder for KeycapMeshBuilder {
+    fn build(&self) -> Mesh {
+        debug_assert!(self.segments > 0);
+        let Keycap {
+            frustum,
+            dish,
+            dish_depth,
+        } = self.keycap;
+        let RoundedFrustum {
+            top_size, height, ..
+        } = frustum;
+        debug_assert!(dish_depth < height);
+        let half_height = height / 2.0;
+        let segments = self.segments as u32;
+        let mut mesh = build_frustum(
+            &frustum,
+            self.subdivisions,
+            UVec3::new(segments, segments, 1),
+            &self.options,
+        );
+
+        // Sink each point by the depth of the dish above it, in proportion to its height, so
+        // that the base remains flat. Normals transform by the inverse transpose of the
+        // Jacobian of the mapping, which is linear, so inverted normals stay inverted.
+        let Some(positions) = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3)
+        else {
+            return mesh;
+        };
+        let displaced: Vec<(f32, f32, Vec2)> = positions
+            .iter()
+            .map(|&pos| {
+                let pos = Vec3::from(pos);
+                let (depth, gradient) = dish.depth_at(dish_depth, top_size / 2.0, pos.xy());
+                ((pos.z + half_height) / height, depth, gradient)
+            })
+            .collect();
+        if let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+        {
+            for (pos, &(weight, depth, _)) in positions.iter_mut().zip(&displaced) {
+                pos[2] -= weight * depth;
+            }
+        }
+        if let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute_mut(Mesh::ATTRIBUTE_NORMAL)
+        {
+            for (normal, &(weight, depth, gradient)) in normals.iter_mut().zip(&displaced) {
+                let n = Vec3::from(*normal);
+                let scale = 1.0 - depth / height;
+                *normal = (n.xy() + weight * gradient * n.z / scale)
+                    .extend(n.z / scale)
+                    .normalize()
+                    .to_array();
+            }
+        }
+        mesh
+    }
+}
+
+impl KeycapMeshBuilder {
+    /// Sets the number of subdivisions.
+    pub const fn with_subdivisions(self, subdivisions: usize) -> Self {
+        KeycapMeshBuilder {
+            subdivisions,
+            ..self
+        }
+    }
+
+    /// Sets the number of segments along each edge of the top.
+    pub const fn with_segments(self, segments: usize) -> Self {
+        KeycapMeshBuilder { segments, ..self }
+    }
+
+    /// Sets the mesh generation options.
+    pub const fn with_options(self, options: RoundedBoxMeshOptions) -> Self {
+        KeycapMeshBuilder { options, ..self }
+    }
+
+    /// Turn the mesh inside out, for viewing from inside.
+    pub const fn with_inverted(mut self) -> Self {
+        self.options = self.options.with_inverted();
+        self
+    }
+
+    /// Omit the flat quad of a face, numbered as for [`ATTRIBUTE_FACE`](crate::ATTRIBUTE_FACE).
+    pub const fn without_face(mut self, face: u32) -> Self {
+        self.options = self.options.without_face(face);
+        self
+    }
+
+    /// Enable generating [`Mesh::ATTRIBUTE_UV_0`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub const fn with_uv(mut self) -> Self {
+        self.options = self.options.with_uv();
+        self
+    }
+
+    /// Enable generating [`ATTRIBUTE_FACE`](crate::ATTRIBUTE_FACE). Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub const fn with_face(mut self) -> Self {
+        self.options = self.options.with_face();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    #[cfg(feature = "uvf")]
+    use crate::ATTRIBUTE_FACE;
+
+    #[test]
+    fn test_keycap() {
+        let frustum = RoundedFrustum::new(Vec2::new(1.0, 0.9), Vec2::new(0.7, 0.6), 0.5, 0.05);
+        for subdivisions in 1..=4 {
+            for segments in [1, 2, 5, 8] {
+                for options in MESH_OPTIONS {
+                    for dish in [KeycapDish::Cylindrical, KeycapDish::Spherical] {
+                        println!(
+                            "subdivisions={} segments={} options={:?} dish={:?}",
+                            subdivisions, segments, options, dish
+                        );
+                        let mesh = Keycap::new(frustum, dish, 0.03)
+                            .mesh()
+                            .with_subdivisions(subdivisions)
+                            .with_segments(segments)
+                            .with_options(options)
+                            .build();
+                        assert_no_degenerates(&mesh);
+                        assert_no_duplicates(&mesh);
+                        assert_unit_normals(&mesh);
+                        assert_outward_normals(&mesh);
+                        assert_closed(&mesh);
+
+                        // The dish is deepest at the centre of the top and the base is flat
+                        let pos = vertex_positions(&mesh);
+                        let top = pos.iter().map(|p| p[2]).fold(f32::NEG_INFINITY, f32::max);
+                        assert!(top <= 0.25 + 1e-6, "top={}", top);
+                        if segments % 2 == 0 {
+                            let centre = pos
+                                .iter()
+                                .find(|p| Vec2::new(p[0], p[1]).length() < 1e-6 && p[2] > 0.0);
+                            assert!(
+                                (centre.unwrap()[2] - 0.22).abs() < 1e-6,
+                                "centre={:?}",
+                                centre
+                            );
+                        }
+                        let min_z = pos.iter().map(|p| p[2]).fold(f32::INFINITY, f32::min);
+                        assert!((min_z + 0.25).abs() < 1e-6, "min_z={}", min_z);
+
+                        #[cfg(feature = "uvf")]
+                        if let (
+                            Some(VertexAttributeValues::Uint32(faces)),
+                            Some(VertexAttributeValues::Float32x2(uvs)),
+                        ) = (
+                            mesh.attribute(ATTRIBUTE_FACE),
+                            mesh.attribute(Mesh::ATTRIBUTE_UV_0),
+                        ) {
+                            assert!(faces.iter().all(|&face| face < 6));
+                            for &uv in uvs {
+                                let uv = Vec2::from(uv);
+                                assert!(uv.cmpge(Vec2::splat(-1e-5)).all(), "uv={}", uv);
+                                assert!(uv.cmple(Vec2::splat(1.0 + 1e-5)).all(), "uv={}", uv);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Any face can be omitted, leaving no triangles in the base
+        let mesh = Keycap::default().mesh().without_face(5).build();
+        let pos = vertex_positions(&mesh);
+        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+        assert!(!indices.is_empty());
+        assert!(indices
+            .chunks_exact(3)
+            .all(|triangle| triangle.iter().any(|&i| pos[i][2] > -0.25 + 1e-6)));
+    }
+}
@@ -1,14 +1,43 @@
-use std::f32::consts::{PI, TAU};
+#[cfg(feature = "uvf")]
+use std::f32::consts::TAU;
+use std::{collections::BTreeMap, f32::consts::FRAC_PI_2, sync::Arc};
 
 use bevy::{
+    math::curve::Curve,
     prelude::*,
     render::{
-        mesh::{Indices, MeshVertexAttribute, PrimitiveTopology},
+        mesh::{Indices, MeshVertexAttribute, PrimitiveTopology, VertexAttributeValues},
         render_asset::RenderAssetUsages,
         render_resource::VertexFormat,
     },
 };
 
+mod extrude;
+mod keycap;
+mod quad_sphere;
+mod rounded_container;
+mod rounded_convex;
+mod rounded_cylinder;
+mod rounded_extrusion;
+mod rounded_frame;
+mod rounded_frustum;
+mod rounded_prism;
+mod rounded_rect;
+#[cfg(test)]
+mod test_helpers;
+
+pub use keycap::{Keycap, KeycapDish, KeycapMeshBuilder};
+pub use rounded_container::{RoundedContainer, RoundedContainerMeshBuilder};
+pub use rounded_convex::{RoundedConvex, RoundedConvexMeshBuilder};
+pub use rounded_cylinder::{RoundedCylinder, RoundedCylinderMeshBuilder};
+pub use rounded_extrusion::{RoundedExtrusion, RoundedExtrusionMeshBuilder};
+pub use rounded_frame::{RoundedBoxFrame, RoundedBoxFrameMeshBuilder};
+pub use rounded_frustum::{RoundedFrustum, RoundedFrustumMeshBuilder};
+pub use rounded_prism::{RoundedPrism, RoundedPrismMeshBuilder};
+pub use rounded_rect::{RoundedRect, RoundedRectMeshBuilder};
+
+use quad_sphere::build_quad_sphere_topology;
+
 #[derive(Copy, Clone)]
 struct XYQuarter(u32);
 
@@ -22,6 +51,54 @@ impl XYQuarter {
             _ => unreachable!(),
         }
     }
+
+    fn from_coords(coords: Vec2) -> Self {
+        match (coords.x >= 0.0, coords.y >= 0.0) {
+            (true, true) => XYQuarter(0),
+            (false, true) => XYQuarter(1),
+            (false, false) => XYQuarter(2),
+            (true, false) => XYQuarter(3),
+        }
+    }
+}
+
+/// Returns the number of segments across the flat regions of the faces, which is at least one,
+/// and only one along an axis where the corners given by `vertex` meet, so that the vertices of
+/// the regions don't pile up at the same point.
+fn flat_segments(segments: UVec3, vertex: &mut impl FnMut(Vec3, Vec3) -> (Vec3, Vec3)) -> UVec3 {
+    let corners = (0..8u32).map(|i| {
+        Vec3::select(
+            BVec3::new(i & 1 == 0, i & 2 == 0, i & 4 == 0),
+            Vec3::ONE,
+            Vec3::NEG_ONE,
+        )
+    });
+    UVec3::from_array([0, 1, 2].map(|axis| {
+        let meet = corners.clone().all(|corner| {
+            let mut mirror = corner;
+            mirror[axis] = -corner[axis];
+            [Vec3::X, Vec3::Y, Vec3::Z]
+                .into_iter()
+                .filter(|dir| dir[axis] == 0.0)
+                .all(|dir| vertex(corner, dir).0[axis] == vertex(mirror, dir).0[axis])
+        });
+        if meet {
+            1
+        } else {
+            segments[axis].max(1)
+        }
+    }))
+}
+
+/// Returns the cosine and sine of `step` steps of a right angle divided into `quarter_steps`.
+///
+/// They are exact on the axes, so that corners which meet there share the same points.
+fn right_angle_unit(step: i32, quarter_steps: i32) -> Vec2 {
+    if step % quarter_steps == 0 {
+        [Vec2::X, Vec2::Y, Vec2::NEG_X, Vec2::NEG_Y][(step / quarter_steps).rem_euclid(4) as usize]
+    } else {
+        Vec2::from_angle(step as f32 * FRAC_PI_2 / quarter_steps as f32)
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -47,24 +124,27 @@ impl ZHalf {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq)]
-enum StackType {
-    Ultimate(ZHalf),
-    Penultimate(ZHalf),
-    Ordinary,
-}
-
+/// The layout of the vertices of a [`RoundedBox`].
+///
+/// The corners form a lattice of `sectors` and `stacks`, indexed as for a UV sphere whose poles
+/// are stretched into the top and bottom faces. The flat regions between the corners are
+/// divided into `segments` along each axis, whose vertices are interpolated between those of
+/// the lattice around them.
+///
+/// The vertices are laid out as the inside of the top grid, the rings from the edge of the top
+/// grid to the edge of the bottom grid, and the inside of the bottom grid. Within the rings
+/// around the grids, the sectors of each corner meet at a single vertex.
 struct PhysicalIndexer {
-    subdivisions: u32,
+    sector_subdivisions: u32,
+    #[cfg_attr(not(feature = "uvf"), allow(dead_code))]
+    stack_subdivisions: u32,
     extra_levels: u32,
     sectors: u32,
     stacks: u32,
+    segments: UVec3,
 }
 
 impl PhysicalIndexer {
-    const ULTIMATE_SECTORS: u32 = 1;
-    const PENULTIMATE_SECTORS: u32 = 4;
-    const TOTAL_END_SECTORS: u32 = Self::ULTIMATE_SECTORS + Self::PENULTIMATE_SECTORS;
     const END_STACKS: u32 = 2;
     const BOTH_END_STACKS: u32 = 2 * Self::END_STACKS;
 
@@ -76,169 +156,285 @@ impl PhysicalIndexer {
         (clamped - z_half, ZHalf::from(z_half / self.extra_levels))
     }
 
-    fn stack_type(&self, stack: u32) -> StackType {
-        debug_assert!(stack < self.stacks);
-        if stack == 0 {
-            StackType::Ultimate(ZHalf::Top)
-        } else if stack == self.stacks - 1 {
-            StackType::Ultimate(ZHalf::Bottom)
-        } else if stack == 1 {
-            StackType::Penultimate(ZHalf::Top)
-        } else if stack == self.stacks - 2 {
-            StackType::Penultimate(ZHalf::Bottom)
+    fn decode_sector(&self, sector: u32) -> (u32, XYQuarter) {
+        debug_assert!(sector < self.sectors);
+        let xy_quarter = sector / (self.corner_sectors() / self.extra_levels);
+        (
+            sector - xy_quarter,
+            XYQuarter(xy_quarter / self.extra_levels),
+        )
+    }
+
+    /// Returns the number of sectors of the lattice in each corner.
+    fn corner_sectors(&self) -> u32 {
+        self.sectors / 4
+    }
+
+    /// Returns the number of segments along the side face which follows `quarter`.
+    fn edge_segments(&self, quarter: u32) -> u32 {
+        if quarter % 2 == 0 {
+            self.segments.x
         } else {
-            StackType::Ordinary
+            self.segments.y
         }
     }
 
-    fn decode_sector(&self, sector: u32, stack: u32) -> (u32, XYQuarter) {
-        let quarter = self.sectors / 4;
-        match self.stack_type(stack) {
-            StackType::Ultimate(_) => {
-                debug_assert!(sector == 0);
-                (0, XYQuarter(0))
-            }
-            StackType::Penultimate(_) => {
-                debug_assert!(sector < Self::PENULTIMATE_SECTORS);
-                (sector * (quarter - self.extra_levels), XYQuarter(sector))
-            }
-            StackType::Ordinary => {
-                debug_assert!(sector < self.sectors);
-                let xy_quarter = sector / (quarter / self.extra_levels);
-                (
-                    sector - xy_quarter,
-                    XYQuarter(xy_quarter / self.extra_levels),
-                )
+    /// Returns the number of vertices in a ring between the top and bottom grids.
+    fn ring_sectors(&self) -> u32 {
+        self.sectors - 4 + self.cap_sectors()
+    }
+
+    /// Returns the number of vertices around the edge of the top or bottom grid.
+    fn cap_sectors(&self) -> u32 {
+        2 * (self.segments.x + self.segments.y)
+    }
+
+    /// Returns the number of vertices inside the top or bottom grid.
+    fn cap_interior(&self) -> u32 {
+        (self.segments.x - 1) * (self.segments.y - 1)
+    }
+
+    /// Returns the number of rings, including those around the top and bottom grids.
+    fn rings(&self) -> u32 {
+        self.stacks - 2 + self.segments.z - 1
+    }
+
+    /// Returns the quarter a sector of a ring belongs to, and its offset within the quarter.
+    ///
+    /// Each quarter has the sectors of its corner followed by those which divide the side face
+    /// after it.
+    fn decode_ring_sector(&self, sector: u32) -> (u32, u32) {
+        let mut offset = sector % self.ring_sectors();
+        for quarter in 0..4 {
+            let quarter_sectors = self.corner_sectors() + self.edge_segments(quarter) - 1;
+            if offset < quarter_sectors {
+                return (quarter, offset);
             }
+            offset -= quarter_sectors;
         }
+        unreachable!()
     }
 
-    fn sectors(&self, stack: u32) -> u32 {
-        match self.stack_type(stack) {
-            StackType::Ultimate(_) => Self::ULTIMATE_SECTORS,
-            StackType::Penultimate(_) => Self::PENULTIMATE_SECTORS,
-            StackType::Ordinary => self.sectors,
+    /// Returns the sectors of the lattice a sector of a ring is interpolated between, and
+    /// their weights.
+    fn column_sources(&self, sector: u32) -> [(u32, f32); 2] {
+        let corner_sectors = self.corner_sectors();
+        let (quarter, offset) = self.decode_ring_sector(sector);
+        let start = quarter * corner_sectors;
+        if offset < corner_sectors {
+            [(start + offset, 1.0), (start + offset, 0.0)]
+        } else {
+            let fraction =
+                (offset + 1 - corner_sectors) as f32 / self.edge_segments(quarter) as f32;
+            [
+                (start + corner_sectors - 1, 1.0 - fraction),
+                ((start + corner_sectors) % self.sectors, fraction),
+            ]
         }
     }
 
-    fn stretch_xy(&self, stack: u32) -> bool {
-        match self.stack_type(stack) {
-            StackType::Ultimate(_) => false,
-            StackType::Penultimate(_) | StackType::Ordinary => true,
+    /// Returns the stacks of the lattice a ring is interpolated between, and their weights.
+    fn row_sources(&self, ring: u32) -> [(u32, f32); 2] {
+        let half = (self.stacks - Self::BOTH_END_STACKS) / 2;
+        if ring <= half {
+            [(ring + 1, 1.0), (ring + 1, 0.0)]
+        } else if ring < half + self.segments.z {
+            let fraction = (ring - half) as f32 / self.segments.z as f32;
+            [(half + 1, 1.0 - fraction), (half + 2, fraction)]
+        } else {
+            let stack = ring + 2 - self.segments.z;
+            [(stack, 1.0), (stack, 0.0)]
         }
     }
 
-    fn index(&self, sector: u32, stack: u32) -> u32 {
-        let quarter = self.sectors / Self::PENULTIMATE_SECTORS;
-        match self.stack_type(stack) {
-            StackType::Ultimate(ZHalf::Top) => 0,
-            StackType::Penultimate(ZHalf::Top) => {
-                Self::ULTIMATE_SECTORS + ((sector / quarter) % Self::PENULTIMATE_SECTORS)
-            }
-            StackType::Ordinary => {
-                Self::TOTAL_END_SECTORS
-                    + (stack - Self::END_STACKS) * self.sectors
-                    + (sector % self.sectors)
-            }
-            StackType::Penultimate(ZHalf::Bottom) => {
-                Self::TOTAL_END_SECTORS
-                    + (self.stacks - Self::BOTH_END_STACKS) * self.sectors
-                    + ((sector / quarter) % Self::PENULTIMATE_SECTORS)
-            }
-            StackType::Ultimate(ZHalf::Bottom) => {
-                Self::TOTAL_END_SECTORS
-                    + Self::PENULTIMATE_SECTORS
-                    + (self.stacks - Self::BOTH_END_STACKS) * self.sectors
+    /// Returns the index of the vertex of a ring, where the sectors of each corner meet in the
+    /// rings around the top and bottom grids.
+    fn ring_index(&self, sector: u32, ring: u32) -> u32 {
+        let first_ring = self.cap_interior();
+        let last_ring = first_ring + self.cap_sectors() + (self.rings() - 2) * self.ring_sectors();
+        if ring == 0 || ring == self.rings() - 1 {
+            let (quarter, offset) = self.decode_ring_sector(sector);
+            let start: u32 = (0..quarter)
+                .map(|quarter| self.edge_segments(quarter))
+                .sum();
+            let cap_sector = start + offset.saturating_sub(self.corner_sectors() - 1);
+            if ring == 0 {
+                first_ring + cap_sector
+            } else {
+                last_ring + cap_sector
             }
+        } else {
+            first_ring
+                + self.cap_sectors()
+                + (ring - 1) * self.ring_sectors()
+                + sector % self.ring_sectors()
+        }
+    }
+
+    /// Returns the index of the vertex at `i` and `j` along the X and Y axes of the top or
+    /// bottom grid.
+    fn grid_index(&self, i: u32, j: u32, z_half: ZHalf) -> u32 {
+        let UVec3 { x, y, .. } = self.segments;
+        if i == 0 || j == 0 || i == x || j == y {
+            // Anticlockwise around the ring, starting from the +X+Y corner
+            let cap_sector = if j == y {
+                x - i
+            } else if i == 0 {
+                x + y - j
+            } else if j == 0 {
+                x + y + i
+            } else {
+                2 * x + y + j
+            };
+            let ring = match z_half {
+                ZHalf::Top => 0,
+                ZHalf::Bottom => self.rings() - 1,
+            };
+            self.ring_index(0, ring) + cap_sector
+        } else {
+            let start = match z_half {
+                ZHalf::Top => 0,
+                ZHalf::Bottom => self.total_vertices() as u32 - self.cap_interior(),
+            };
+            start + (j - 1) * (x - 1) + (i - 1)
         }
     }
 
     fn total_vertices(&self) -> usize {
-        (self.sectors * (self.stacks - Self::BOTH_END_STACKS) + 2 * Self::TOTAL_END_SECTORS)
-            as usize
+        (2 * self.cap_interior()
+            + 2 * self.cap_sectors()
+            + (self.rings() - 2) * self.ring_sectors()) as usize
     }
 
     fn total_indices(&self) -> usize {
-        6 * ((self.sectors - Self::PENULTIMATE_SECTORS * (self.extra_levels - 1))
-            * (self.stacks - (Self::BOTH_END_STACKS - 1) - 2 * (self.extra_levels - 1))
-            - (Self::PENULTIMATE_SECTORS * (self.subdivisions - 1))) as usize
+        let split_levels = self.extra_levels - 1;
+        let cap_triangles = 2 * self.segments.x * self.segments.y;
+        let end_ring_triangles = 4 * self.sector_subdivisions + 2 * self.cap_sectors();
+        let ring_triangles = 2 * (self.ring_sectors() - 4 * split_levels);
+        let ordinary_rings = self.rings() - 3 - 2 * split_levels;
+        3 * (2 * (cap_triangles + end_ring_triangles) + ring_triangles * ordinary_rings) as usize
+    }
+
+    /// Returns the number of indices in the flat region of a face.
+    fn flat_face_indices(&self, face: u32) -> usize {
+        let UVec3 { x, y, z } = self.segments;
+        6 * match face {
+            0 | 5 => x * y,
+            1 | 3 => x * z,
+            _ => y * z,
+        } as usize
+    }
+
+    /// Returns the face whose flat region contains the cell between `sector` and `ring` and
+    /// the next sector and ring, if any.
+    fn flat_face(&self, sector: u32, ring: u32) -> Option<u32> {
+        let half = (self.stacks - Self::BOTH_END_STACKS) / 2;
+        if ring < half || ring >= half + self.segments.z {
+            return None;
+        }
+        let (quarter, offset) = self.decode_ring_sector(sector);
+        (offset + 1 >= self.corner_sectors()).then_some(1 + quarter)
     }
 
     #[cfg(feature = "uvf")]
     fn face(&self, sector: u32, stack: u32) -> u32 {
-        let half_subdivisions = self.subdivisions / 2;
-        if stack < Self::END_STACKS + half_subdivisions {
+        let half_stacks = self.stack_subdivisions / 2;
+        if stack < Self::END_STACKS + half_stacks {
             0
-        } else if stack > self.stacks - Self::END_STACKS - half_subdivisions - 1 {
+        } else if stack > self.stacks - Self::END_STACKS - half_stacks - 1 {
             5
         } else {
-            1 + ((sector + self.sectors - half_subdivisions - 1) / (self.sectors / 4)) % 4
+            let half_sectors = self.sector_subdivisions / 2;
+            1 + ((sector + self.sectors - half_sectors - 1) / (self.sectors / 4)) % 4
         }
     }
 
     #[cfg(feature = "uvf")]
-    fn uv_coords(&self, rounded_length: f32, core_size: Vec3, sector: u32, stack: u32) -> Vec2 {
-        let half_subdivisions = self.subdivisions / 2;
-        let (logical_sector, xy_quarter) = self.decode_sector(sector, stack);
+    fn uv_coords(&self, edges: &BoxEdges, sector: u32, stack: u32) -> Vec2 {
+        let half_sectors = self.sector_subdivisions / 2;
+        let half_stacks = self.stack_subdivisions / 2;
+        let (logical_sector, xy_quarter) = self.decode_sector(sector);
         let face = self.face(sector, stack);
         match face {
-            0 | 5 => match self.stack_type(stack) {
-                StackType::Ultimate(_) => Vec2::new(0.5, 0.5),
-                StackType::Penultimate(_) | StackType::Ordinary => {
-                    let dist = (stack
-                        .min(self.stacks - stack - 1)
-                        .clamp(1, half_subdivisions + 1)
-                        - 1) as f32
-                        / half_subdivisions as f32;
-                    let corner_sector = logical_sector % self.subdivisions;
-                    let octant = logical_sector / half_subdivisions;
-                    let mirrored_sector = if corner_sector <= half_subdivisions {
-                        corner_sector
-                    } else {
-                        2 * half_subdivisions - corner_sector
-                    };
-                    let edge_len = mirrored_sector as f32 / half_subdivisions as f32;
-                    let edge_vec = if octant.div_ceil(2) % 2 == 1 {
-                        Vec2::new(edge_len, 1.0)
-                    } else {
-                        Vec2::new(1.0, edge_len)
-                    };
-                    let unmirror = match face {
-                        0 => Vec2::new(1.0, -1.0),
-                        _ => Vec2::ONE,
-                    };
-                    let v = unmirror
-                        * xy_quarter.coords()
-                        * (dist * edge_vec * rounded_length + 0.5 * core_size.truncate());
-                    0.5 + (v / (core_size.truncate() + 2.0 * rounded_length))
+            0 | 5 => {
+                let z = if face == 0 { 1.0 } else { -1.0 };
+                let rounded_len = |coords: Vec2| {
+                    Vec2::new(
+                        edges.rounded_length(1, Vec3::new(coords.x, 0.0, z), 0),
+                        edges.rounded_length(0, Vec3::new(0.0, coords.y, z), 1),
+                    )
+                };
+                let core_len = |coords: Vec2| {
+                    Vec2::new(
+                        edges.core_extent(1, Vec3::new(coords.x, 0.0, z), 0),
+                        edges.core_extent(0, Vec3::new(0.0, coords.y, z), 1),
+                    )
+                };
+                let min = -core_len(-Vec2::ONE) - rounded_len(-Vec2::ONE);
+                let max = core_len(Vec2::ONE) + rounded_len(Vec2::ONE);
+                let dist = (stack.min(self.stacks - stack - 1).clamp(1, half_stacks + 1) - 1)
+                    as f32
+                    / half_stacks as f32;
+                let corner_sector = logical_sector % self.sector_subdivisions;
+                let octant = logical_sector / half_sectors;
+                let mirrored_sector = if corner_sector <= half_sectors {
+                    corner_sector
+                } else {
+                    2 * half_sectors - corner_sector
+                };
+                let edge_len = mirrored_sector as f32 / half_sectors as f32;
+                let edge_vec = if octant.div_ceil(2) % 2 == 1 {
+                    Vec2::new(edge_len, 1.0)
+                } else {
+                    Vec2::new(1.0, edge_len)
+                };
+                let coords = xy_quarter.coords();
+                let v = coords * (dist * edge_vec * rounded_len(coords) + core_len(coords));
+                let uv = (v - min) / (max - min);
+                match face {
+                    0 => Vec2::new(uv.x, 1.0 - uv.y),
+                    _ => uv,
                 }
-            },
+            }
             1..=4 => {
-                let u_core_len = match face {
-                    1 | 3 => core_size.x,
-                    2 | 4 => core_size.y,
+                let (u_axis, edge_axis) = match face {
+                    1 | 3 => (0, 0),
+                    2 | 4 => (1, 1),
                     _ => unreachable!(),
                 };
-                let u_offset = (4 * self.subdivisions + half_subdivisions + logical_sector
-                    - face * self.subdivisions)
-                    % (4 * self.subdivisions);
-                let u_off_len = rounded_length * u_offset as f32 / half_subdivisions as f32
-                    + if face % 4 == xy_quarter.0 {
-                        u_core_len
-                    } else {
-                        0.0
-                    };
-                let v_offset = stack - half_subdivisions - 2;
-                let v_off_len = rounded_length
-                    * ((v_offset % (half_subdivisions + 1)) as f32 / half_subdivisions as f32)
-                    + if v_offset > half_subdivisions {
-                        core_size.z + rounded_length
-                    } else {
-                        0.0
-                    };
+                let u_start = XYQuarter(face - 1).coords().extend(0.0);
+                let u_end = XYQuarter(face).coords().extend(0.0);
+                let u_start_len = edges.rounded_length(2, u_start, u_axis);
+                let u_end_len = edges.rounded_length(2, u_end, u_axis);
+                let u_core_len =
+                    edges.core_extent(2, u_start, u_axis) + edges.core_extent(2, u_end, u_axis);
+                let u_offset = (4 * self.sector_subdivisions + half_sectors + logical_sector
+                    - face * self.sector_subdivisions)
+                    % (4 * self.sector_subdivisions);
+                let u_off_len = if face % 4 == xy_quarter.0 {
+                    u_start_len
+                        + u_core_len
+                        + u_end_len * (u_offset - half_sectors) as f32 / half_sectors as f32
+                } else {
+                    u_start_len * u_offset as f32 / half_sectors as f32
+                };
+                let side = (u_start + u_end) / 2.0;
+                let v_start = side.with_z(1.0);
+                let v_end = side.with_z(-1.0);
+                let v_start_len = edges.rounded_length(edge_axis, v_start, 2);
+                let v_end_len = edges.rounded_length(edge_axis, v_end, 2);
+                let v_core_len = edges.core_extent(edge_axis, v_start, 2)
+                    + edges.core_extent(edge_axis, v_end, 2);
+                let v_offset = stack - half_stacks - 2;
+                let v_fraction = (v_offset % (half_stacks + 1)) as f32 / half_stacks as f32;
+                let v_off_len = if v_offset > half_stacks {
+                    v_start_len + v_core_len + v_end_len * v_fraction
+                } else {
+                    v_start_len * v_fraction
+                };
                 Vec2::new(
-                    u_off_len / (u_core_len + 2.0 * rounded_length),
-                    v_off_len / (core_size.z + 2.0 * rounded_length),
+                    u_off_len / (u_start_len + u_core_len + u_end_len),
+                    v_off_len / (v_start_len + v_core_len + v_end_len),
                 )
             }
             _ => unreachable!(),
@@ -246,291 +442,1311 @@ impl PhysicalIndexer {
     }
 }
 
-/// The index of the box face the vertex belongs to.
-///
-/// The +Z and -Z faces are numbered 0 and 5. The faces on the sides are numbered 1 to 4.
-pub const ATTRIBUTE_FACE: MeshVertexAttribute =
-    MeshVertexAttribute::new("Face", 1554371710, VertexFormat::Uint32);
-
-/// Options for generating the mesh of a [`RoundedBox`]
-#[derive(Copy, Clone, Debug, Default)]
-pub struct RoundedBoxMeshOptions {
-    #[cfg(feature = "uvf")]
-    generate_uv: bool,
-    #[cfg(feature = "uvf")]
-    generate_face: bool,
+/// Returns whether the radii of an edge are negative, which curves it inwards.
+fn is_cove(radius: Vec3) -> bool {
+    radius.max_element() < 0.0
 }
 
-impl RoundedBoxMeshOptions {
-    /// Default mesh options.
-    pub const DEFAULT: Self = RoundedBoxMeshOptions {
-        #[cfg(feature = "uvf")]
-        generate_uv: false,
-        #[cfg(feature = "uvf")]
-        generate_face: false,
-    };
-
-    /// Enable generating [`Mesh::ATTRIBUTE_UV_0`]. Requires `uvf` feature.
-    #[cfg(feature = "uvf")]
-    pub const fn with_uv(self) -> Self {
-        RoundedBoxMeshOptions {
-            generate_uv: true,
-            ..self
-        }
-    }
+/// The radii of the edges of a box, indexed by the axis each edge is parallel to and then by
+/// its quarter in the plane of the other two axes.
+struct BoxEdges {
+    half_size: Vec3,
+    radii: [[Vec3; 4]; 3],
+}
 
-    /// Enable generating [`ATTRIBUTE_FACE`]. Requires `uvf` feature.
-    #[cfg(feature = "uvf")]
-    pub const fn with_face(self) -> Self {
-        RoundedBoxMeshOptions {
-            generate_face: true,
-            ..self
-        }
+impl BoxEdges {
+    /// Returns the radii of the edge parallel to `axis` which passes nearest to `corner`.
+    fn edge(&self, axis: usize, corner: Vec3) -> Vec3 {
+        let coords = match axis {
+            0 => corner.yz(),
+            1 => corner.zx(),
+            _ => corner.xy(),
+        };
+        self.radii[axis][XYQuarter::from_coords(coords).0 as usize]
     }
 
-    fn is_generate_uv(&self) -> bool {
-        #[cfg(feature = "uvf")]
-        {
-            self.generate_uv
+    /// Returns the inset from the outermost corner of the box and the outward normal of a
+    /// corner at the point which corresponds to `dir` on the unit sphere. Both are expressed
+    /// in the octant of `corner`, so that they point away from the centre of the box.
+    ///
+    /// The corner meets each of its three edges exactly and reduces to an octant of an
+    /// ellipsoid, or of a superellipsoid for [`CornerProfile::Superellipse`], when the radii of
+    /// its edges agree.
+    fn corner_point(&self, corner: Vec3, dir: Vec3, profile: &CornerProfile) -> (Vec3, Vec3) {
+        let [x_edge, y_edge, z_edge] = [0, 1, 2].map(|axis| self.edge(axis, corner));
+        let dir = dir.abs();
+        // A cove follows the profile mirrored across the diagonal of its square, so that the
+        // sweep around a corner follows its vertical edge and the sweep over it the top edges
+        let cove = |(point, tangent): (Vec2, Vec2), cove: bool| {
+            if cove {
+                (Vec2::ONE - point.yx(), -tangent.yx())
+            } else {
+                (point, tangent)
+            }
+        };
+        let theta_cove = is_cove(z_edge);
+        let phi_cove = is_cove(x_edge + y_edge);
+        let [mut x_edge, mut y_edge, z_edge] = [x_edge, y_edge, z_edge].map(Vec3::abs);
+        // Where a sharp top edge meets a rounded vertical edge, the corner flattens into the top
+        // face. It is swept as if the top edge had the radius of the vertical edge, so that it
+        // stays within the outline of the vertical edge.
+        if y_edge.x == 0.0 {
+            y_edge.x = z_edge.x;
         }
-        #[cfg(not(feature = "uvf"))]
-        {
-            false
+        if x_edge.y == 0.0 {
+            x_edge.y = z_edge.y;
         }
-    }
+        let unit_phi = Vec2::new(dir.xy().length(), dir.z);
+        let unit_theta = if unit_phi.x > 0.0 {
+            dir.xy() / unit_phi.x
+        } else {
+            Vec2::X
+        };
+        let (theta, theta_tangent) = cove(profile.point(unit_theta), theta_cove);
+        let (phi, phi_tangent) = cove(profile.point(unit_phi), phi_cove);
+        let top_weight = unit_theta.x * unit_theta.x;
+        let top_radius = y_edge.z * top_weight + x_edge.z * (1.0 - top_weight);
+        let inset = Vec3::new(
+            z_edge.x * phi.x * (1.0 - theta.x) + y_edge.x * (1.0 - phi.x),
+            z_edge.y * phi.x * (1.0 - theta.y) + x_edge.y * (1.0 - phi.x),
+            top_radius * (1.0 - phi.y),
+        );
 
-    fn is_generate_face(&self) -> bool {
-        #[cfg(feature = "uvf")]
-        {
-            self.generate_face
-        }
-        #[cfg(not(feature = "uvf"))]
-        {
-            false
-        }
-    }
+        // The derivative along the sector is divided by `phi.x` so it remains defined at the
+        // pole. Both derivatives share the scale of the profile's tangents.
+        let top_weight_derivative = profile.weight_derivative(unit_theta);
+        let pole_ratio = if phi.x > 0.0 {
+            (1.0 - phi.y) / phi.x
+        } else {
+            0.0
+        };
+        let along_sector = Vec3::new(
+            -z_edge.x * theta_tangent.x,
+            -z_edge.y * theta_tangent.y,
+            (y_edge.z - x_edge.z) * top_weight_derivative * pole_ratio,
+        );
+        let along_stack = Vec3::new(
+            phi_tangent.x * (z_edge.x * (1.0 - theta.x) - y_edge.x),
+            phi_tangent.x * (z_edge.y * (1.0 - theta.y) - x_edge.y),
+            -top_radius * phi_tangent.y,
+        );
 
-    fn is_split_faces(&self) -> bool {
-        self.is_generate_uv() || self.is_generate_face()
+        // A cove meets the faces and the straight part of its edge at creases, where the normal
+        // is that of the face or edge so that the regions between the corners are shaded
+        // correctly. The triangles of the corner are given their own vertices there.
+        let at_equator = unit_phi.y < 1e-6;
+        let at_meridian = unit_theta.min_element() < 1e-6;
+        if theta_cove || phi_cove {
+            if at_equator && at_meridian {
+                return (inset, unit_theta.round().extend(0.0));
+            }
+            if phi_cove && unit_phi.x < 1e-6 {
+                return (inset, Vec3::Z);
+            }
+        }
+        let along_sector = match (phi_cove && at_meridian, unit_theta.x > unit_theta.y) {
+            (true, true) => Vec3::NEG_Y,
+            (true, false) => Vec3::X,
+            (false, _) => along_sector,
+        };
+        let along_stack = if theta_cove && at_equator {
+            Vec3::NEG_Z
+        } else {
+            along_stack
+        };
+        // Where a sharp edge flattens the stacks into a face, both derivatives lie in the face
+        // and their cross product may point inwards
+        let normal = along_sector
+            .cross(along_stack)
+            .try_normalize()
+            .unwrap_or(dir);
+        let normal = if normal.dot(dir) < 0.0 {
+            -normal
+        } else {
+            normal
+        };
+        (inset, normal)
     }
-}
-
-/// A rounded box.
-#[derive(Copy, Clone, Debug)]
-pub struct RoundedBox {
-    /// The dimensions of the box.
-    pub size: Vec3,
-    /// The radius of the corners and edges.
-    pub radius: f32,
-}
 
-impl Default for RoundedBox {
-    fn default() -> Self {
-        Self {
-            size: Vec3::ONE,
-            radius: 0.1,
+    /// Returns the edges of a surface inset by `thickness`, whose radii shrink by the same
+    /// amount and whose coves grow by it.
+    fn inset(&self, thickness: f32) -> Self {
+        BoxEdges {
+            half_size: self.half_size - thickness,
+            radii: self.radii.map(|axis| {
+                axis.map(|radius| {
+                    if is_cove(radius) {
+                        radius - thickness
+                    } else {
+                        (radius - thickness).max(Vec3::ZERO)
+                    }
+                })
+            }),
         }
     }
-}
 
-impl Meshable for RoundedBox {
-    type Output = RoundedBoxMeshBuilder;
+    /// Returns whether any edge is a cove or sharp, leaving a crease along it.
+    fn has_crease(&self) -> bool {
+        self.radii
+            .iter()
+            .flatten()
+            .any(|&radius| is_cove(radius) || radius == Vec3::ZERO)
+    }
 
-    fn mesh(&self) -> Self::Output {
-        RoundedBoxMeshBuilder {
-            rounded_box: *self,
-            subdivisions: 4,
-            options: RoundedBoxMeshOptions::DEFAULT,
-        }
+    /// Returns the length of the half of an edge's arc which is mapped onto a face, measured
+    /// in the direction of `along`.
+    #[cfg(feature = "uvf")]
+    fn rounded_length(&self, axis: usize, corner: Vec3, along: usize) -> f32 {
+        0.125 * TAU * self.edge(axis, corner)[along].abs()
     }
-}
 
-impl From<RoundedBox> for Mesh {
-    fn from(value: RoundedBox) -> Self {
-        value.mesh().build()
+    /// Returns the distance from the centre of the box to where an edge meets a face,
+    /// measured in the direction of `along`.
+    fn core_extent(&self, axis: usize, corner: Vec3, along: usize) -> f32 {
+        self.half_size[along] - self.edge(axis, corner)[along].abs()
     }
 }
 
-/// A builder used for creating a [`Mesh`] with a [`RoundedBox`] shape.
-#[derive(Copy, Clone, Debug)]
-pub struct RoundedBoxMeshBuilder {
-    /// The [`RoundedBox`] shape.
-    pub rounded_box: RoundedBox,
-    /// The number of sectors and stacks in each corner.
-    pub subdivisions: usize,
-    /// Mesh generation options.
-    pub options: RoundedBoxMeshOptions,
+/// The primitives the surface of a box is built from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum BoxPrimitives {
+    /// Triangles covering the surface.
+    Triangles,
+    /// Lines around the flat regions of the faces, and along the sectors and stacks of the
+    /// rounded regions if `isolines` is set.
+    Lines { isolines: bool },
 }
 
-impl Default for RoundedBoxMeshBuilder {
-    fn default() -> Self {
-        RoundedBox::default().mesh()
+/// Returns the indices of a line list along the sides of quads, each given with the face whose
+/// flat region it lies in, if any.
+///
+/// The sides of flat quads are drawn where they do not meet another quad of the same face, and
+/// those of rounded quads where they meet no other quad, such as the rim of an omitted face,
+/// unless `isolines` draws all of them. Sides are matched by their positions, so that those
+/// shared by the split faces of a mesh or along sharp edges are drawn once.
+fn quad_lines(
+    quads: &[([u32; 4], Option<u32>)],
+    positions: &[[f32; 3]],
+    isolines: bool,
+) -> Vec<u32> {
+    let key = |index: u32| positions[index as usize].map(|c| (c + 0.0).to_bits());
+    let mut sides: BTreeMap<_, ([u32; 2], Vec<Option<u32>>)> = BTreeMap::new();
+    for &(quad, flat_face) in quads {
+        for i in 0..4 {
+            let (a, b) = (quad[i], quad[(i + 1) % 4]);
+            let (key_a, key_b) = (key(a), key(b));
+            if key_a != key_b {
+                let (_, faces) = sides
+                    .entry((key_a.min(key_b), key_a.max(key_b)))
+                    .or_insert(([a, b], Vec::new()));
+                faces.push(flat_face);
+            }
+        }
     }
+    sides
+        .into_values()
+        .filter(|(_, faces)| {
+            let count = |face| faces.iter().filter(|&&other| other == face).count();
+            if faces.iter().any(Option::is_some) {
+                faces.iter().any(|&face| face.is_some() && count(face) == 1)
+            } else {
+                isolines || faces.len() == 1
+            }
+        })
+        .flat_map(|(side, _)| side)
+        .collect()
 }
 
-impl MeshBuilder for RoundedBoxMeshBuilder {
-    // Based on bevy_render::mesh::shape::UVSphere
-    fn build(&self) -> Mesh {
-        debug_assert!(self.subdivisions > 0);
-        let subdivisions = if self.options.is_split_faces() {
-            self.subdivisions + self.subdivisions % 2
-        } else {
-            self.subdivisions
-        } as u32;
-        let logical_sectors = 4 * subdivisions;
-        let logical_stacks = 2 * subdivisions;
-        let extra_levels = if self.options.is_split_faces() { 2 } else { 1 };
-        let physical = PhysicalIndexer {
-            subdivisions,
-            extra_levels,
-            sectors: (logical_sectors + 4 * extra_levels),
-            stacks: (logical_stacks + 2 + 2 * extra_levels),
-        };
+/// Builds a mesh with the topology of a [`RoundedBox`], whose vertices are placed by `vertex`.
+///
+/// `vertex` is given the corner the vertex belongs to and its direction on the unit sphere, and
+/// returns its position and outward normal. The flat regions are divided into `segments` along
+/// each axis. The texture coordinates of each face are laid out as for the edges returned by
+/// `uv_edges`.
+///
+/// An `inner` surface faces the opposite way and numbers its faces from 6. The mesh is a
+/// line list for [`BoxPrimitives::Lines`].
+// Based on bevy_render::mesh::shape::UVSphere
+#[cfg_attr(not(feature = "uvf"), allow(unused_variables))]
+#[allow(clippy::too_many_arguments)]
+fn build_box_topology<'a>(
+    sector_subdivisions: usize,
+    stack_subdivisions: usize,
+    segments: UVec3,
+    options: &RoundedBoxMeshOptions,
+    inner: bool,
+    primitives: BoxPrimitives,
+    mut vertex: impl FnMut(Vec3, Vec3) -> (Vec3, Vec3),
+    uv_edges: impl Fn(u32) -> &'a BoxEdges,
+) -> Mesh {
+    debug_assert!(sector_subdivisions > 0 && stack_subdivisions > 0);
+    let segments = flat_segments(segments, &mut vertex);
+    let [sector_subdivisions, stack_subdivisions] =
+        [sector_subdivisions, stack_subdivisions].map(|subdivisions| {
+            let subdivisions = subdivisions as u32;
+            if options.is_split_faces() {
+                subdivisions + subdivisions % 2
+            } else {
+                subdivisions
+            }
+        });
+    let logical_sectors = 4 * sector_subdivisions;
+    let logical_stacks = 2 * stack_subdivisions;
+    let extra_levels = if options.is_split_faces() { 2 } else { 1 };
+    let physical = PhysicalIndexer {
+        sector_subdivisions,
+        stack_subdivisions,
+        extra_levels,
+        sectors: (logical_sectors + 4 * extra_levels),
+        stacks: (logical_stacks + 2 + 2 * extra_levels),
+        segments,
+    };
 
-        let core_size = self.rounded_box.size - 2.0 * self.rounded_box.radius;
-        let core_offset = core_size / 2.0;
-        let sector_step = TAU / logical_sectors as f32;
-        let stack_step = PI / logical_stacks as f32;
-        #[cfg(feature = "uvf")]
-        let rounded_length = 0.125 * TAU * self.rounded_box.radius;
+    let flip = inner != options.inverted;
+    let facing = if flip { -1.0 } else { 1.0 };
+    #[cfg(feature = "uvf")]
+    let first_face = if inner { 6 } else { 0 };
+
+    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(physical.total_vertices());
+    let mut normals: Vec<[f32; 3]> = Vec::with_capacity(physical.total_vertices());
+    #[cfg(feature = "uvf")]
+    let mut uvs: Vec<[f32; 2]> = Vec::with_capacity(if options.is_generate_uv() {
+        physical.total_vertices()
+    } else {
+        0
+    });
+    #[cfg(feature = "uvf")]
+    let mut faces: Vec<u32> = Vec::with_capacity(if options.is_generate_face() {
+        physical.total_vertices()
+    } else {
+        0
+    });
 
-        let mut positions: Vec<[f32; 3]> = Vec::with_capacity(physical.total_vertices());
-        let mut normals: Vec<[f32; 3]> = Vec::with_capacity(physical.total_vertices());
+    // Generate vertices, interpolating those of the flat regions between the vertices of the
+    // lattice around them. A flat region lies within a single face, so its texture coordinates
+    // are interpolated too.
+    let mut push_vertex = |sources: &[(u32, u32, f32)]| {
+        let mut pos = Vec3::ZERO;
+        let mut normal = Vec3::ZERO;
         #[cfg(feature = "uvf")]
-        let mut uvs: Vec<[f32; 2]> = Vec::with_capacity(if self.options.is_generate_uv() {
-            physical.total_vertices()
-        } else {
-            0
-        });
+        let mut uv = Vec2::ZERO;
         #[cfg(feature = "uvf")]
-        let mut faces: Vec<u32> = Vec::with_capacity(if self.options.is_generate_face() {
-            physical.total_vertices()
-        } else {
-            0
-        });
-
-        // Generate vertices
-        for p_stack in 0..physical.stacks {
+        let face = physical.face(sources[0].0, sources[0].1);
+        for &(p_sector, p_stack, weight) in sources.iter().filter(|source| source.2 > 0.0) {
             let (logical_stack, z_half) = physical.decode_stack(p_stack);
-            let stack_angle = PI / 2. - (logical_stack as f32) * stack_step;
-            let xy = stack_angle.cos();
+            let (logical_sector, xy_quarter) = physical.decode_sector(p_sector);
+
+            // Calculate direction on the unit sphere
+            let sector_unit = right_angle_unit(logical_sector as i32, sector_subdivisions as i32);
+            let stack_unit = right_angle_unit(
+                stack_subdivisions as i32 - logical_stack as i32,
+                stack_subdivisions as i32,
+            );
+            let dir = (stack_unit.x * sector_unit).extend(stack_unit.y);
 
-            // Calculate Z component of normal
-            let normal_z = stack_angle.sin();
+            // Calculate coordinates and normal
+            let corner = xy_quarter.coords().extend(z_half.coord());
+            let (source_pos, source_normal) = vertex(corner, dir);
+            pos += weight * source_pos;
+            normal += weight * source_normal;
 
-            // Calculate Z coordinate
-            let pos_z = self.rounded_box.radius * normal_z + core_offset.z * z_half.coord();
-            let stretch_xy = if physical.stretch_xy(p_stack) {
-                1.0
+            // Calculate texture coordinates
+            #[cfg(feature = "uvf")]
+            if options.is_generate_uv() {
+                uv += weight * physical.uv_coords(uv_edges(face), p_sector, p_stack);
+            }
+        }
+        positions.push(pos.to_array());
+        normals.push((facing * normal.normalize()).to_array());
+        #[cfg(feature = "uvf")]
+        if options.is_generate_uv() {
+            let uv = if options.inverted {
+                Vec2::new(1.0 - uv.x, uv.y)
             } else {
-                0.0
+                uv
             };
+            uvs.push(uv.to_array());
+        }
 
-            for p_sector in 0..physical.sectors(p_stack) {
-                let (logical_sector, xy_quarter) = physical.decode_sector(p_sector, p_stack);
-                let sector_angle = (logical_sector as f32) * sector_step;
-
-                // Calculate X and Y components of normal
-                let normal_xy = xy * Vec2::new(sector_angle.cos(), sector_angle.sin());
-                normals.push(normal_xy.extend(normal_z).to_array());
-
-                // Calculate X and Y coordinates
-                let pos_xy = self.rounded_box.radius * normal_xy
-                    + stretch_xy * core_offset.truncate() * xy_quarter.coords();
-                positions.push(pos_xy.extend(pos_z).to_array());
+        // Calculate face index
+        #[cfg(feature = "uvf")]
+        if options.is_generate_face() {
+            faces.push(first_face + face);
+        }
+    };
+    let corner_sectors = physical.corner_sectors();
+    let grid_sources = |p_stack: u32| {
+        (1..segments.y).flat_map(move |j| {
+            (1..segments.x).map(move |i| {
+                let u = i as f32 / segments.x as f32;
+                let v = j as f32 / segments.y as f32;
+                [
+                    (0, p_stack, u * v),
+                    (corner_sectors, p_stack, (1.0 - u) * v),
+                    (2 * corner_sectors, p_stack, (1.0 - u) * (1.0 - v)),
+                    (3 * corner_sectors, p_stack, u * (1.0 - v)),
+                ]
+            })
+        })
+    };
+    for sources in grid_sources(1) {
+        push_vertex(&sources);
+    }
+    for ring in 0..physical.rings() {
+        let [(stack_a, weight_a), (stack_b, weight_b)] = physical.row_sources(ring);
+        let end_ring = ring == 0 || ring == physical.rings() - 1;
+        for p_sector in 0..physical.ring_sectors() {
+            // The sectors of each corner meet at the edges of the grids
+            let (_, offset) = physical.decode_ring_sector(p_sector);
+            if end_ring && offset > 0 && offset < corner_sectors {
+                continue;
+            }
+            let [(sector_a, column_a), (sector_b, column_b)] = physical.column_sources(p_sector);
+            push_vertex(&[
+                (sector_a, stack_a, column_a * weight_a),
+                (sector_b, stack_a, column_b * weight_a),
+                (sector_a, stack_b, column_a * weight_b),
+                (sector_b, stack_b, column_b * weight_b),
+            ]);
+        }
+    }
+    for sources in grid_sources(physical.stacks - 2) {
+        push_vertex(&sources);
+    }
 
-                // Calculate texture coordinates
-                #[cfg(feature = "uvf")]
-                if self.options.is_generate_uv() {
-                    uvs.push(
-                        physical
-                            .uv_coords(rounded_length, core_size, p_sector, p_stack)
-                            .to_array(),
-                    );
+    // Generate indices, or the quads which lines are drawn along
+    let mut indices: Vec<u32> = Vec::with_capacity(physical.total_indices());
+    let mut quads: Vec<([u32; 4], Option<u32>)> = Vec::new();
+    let lines = primitives != BoxPrimitives::Triangles;
+    for (z_half, face) in [(ZHalf::Top, 0), (ZHalf::Bottom, 5)] {
+        if options.is_face_omitted(face) {
+            continue;
+        }
+        for j in 0..segments.y {
+            for i in 0..segments.x {
+                let jj = physical.grid_index(i, j, z_half);
+                let kj = physical.grid_index(i + 1, j, z_half);
+                let jk = physical.grid_index(i, j + 1, z_half);
+                let kk = physical.grid_index(i + 1, j + 1, z_half);
+                if lines {
+                    quads.push(([jj, kj, kk, jk], Some(face)));
+                    continue;
                 }
-
-                // Calculate face index
-                #[cfg(feature = "uvf")]
-                if self.options.is_generate_face() {
-                    faces.push(physical.face(p_sector, p_stack));
+                let triangles = match z_half {
+                    ZHalf::Top => [[jj, kj, kk], [jj, kk, jk]],
+                    ZHalf::Bottom => [[jj, kk, kj], [jj, jk, kk]],
+                };
+                // Exclude degenerate triangles where the corners meet
+                for triangle in triangles {
+                    let [a, b, c] = triangle.map(|i| positions[i as usize]);
+                    if a != b && b != c && c != a {
+                        indices.extend(triangle);
+                    }
                 }
             }
         }
-
-        // Generate indices
-        let mut indices: Vec<u32> = Vec::with_capacity(physical.total_indices());
-        for p_stack in 0..physical.stacks - 1 {
-            for p_sector in 0..physical.sectors {
-                // Skip degenerate triangles between split faces
-                if self.options.is_split_faces() {
-                    if p_stack == PhysicalIndexer::END_STACKS + subdivisions / 2 - 1
-                        || p_stack
-                            == physical.stacks - PhysicalIndexer::END_STACKS - subdivisions / 2 - 1
-                    {
-                        continue;
-                    }
-                    if (p_sector + (subdivisions + extra_levels) / 2 + 1)
-                        % (subdivisions + extra_levels)
-                        == 0
-                    {
-                        continue;
-                    }
+    }
+    let split_stacks = [
+        PhysicalIndexer::END_STACKS + stack_subdivisions / 2 - 1,
+        physical.stacks - PhysicalIndexer::END_STACKS - stack_subdivisions / 2 - 1,
+    ];
+    for ring in 0..physical.rings() - 1 {
+        let [(stack, _), (_, fraction)] = physical.row_sources(ring);
+        for p_sector in 0..physical.ring_sectors() {
+            // Skip degenerate triangles between split faces
+            if options.is_split_faces() {
+                if fraction == 0.0 && split_stacks.contains(&stack) {
+                    continue;
                 }
-                // Calculate indicies for quad
-                let jj = physical.index(p_sector, p_stack);
-                let jk = physical.index(p_sector, p_stack + 1);
-                let kj = physical.index(p_sector + 1, p_stack);
-                let kk = physical.index(p_sector + 1, p_stack + 1);
-                // Exclude degenerate triangles near the end stacks
-                if (jj != jk) && (jj != kj) && (jk != kj) {
-                    indices.push(jj);
-                    indices.push(jk);
-                    indices.push(kj);
+                if physical.decode_ring_sector(p_sector).1 == corner_sectors / 2 - 1 {
+                    continue;
                 }
-                if (kj != jk) && (kj != kk) && (jk != kk) {
-                    indices.push(kj);
-                    indices.push(jk);
-                    indices.push(kk);
+            }
+            // Skip the flat quads of omitted faces
+            let flat_face = physical.flat_face(p_sector, ring);
+            if flat_face.is_some_and(|face| options.is_face_omitted(face)) {
+                continue;
+            }
+            // Calculate indicies for quad
+            let jj = physical.ring_index(p_sector, ring);
+            let jk = physical.ring_index(p_sector, ring + 1);
+            let kj = physical.ring_index(p_sector + 1, ring);
+            let kk = physical.ring_index(p_sector + 1, ring + 1);
+            if lines {
+                quads.push(([jj, kj, kk, jk], flat_face));
+                continue;
+            }
+            // Exclude degenerate triangles at the edges of the grids and along sharp edges
+            for triangle in [[jj, jk, kj], [kj, jk, kk]] {
+                let [a, b, c] = triangle.map(|i| positions[i as usize]);
+                if a != b && b != c && c != a {
+                    indices.extend(triangle);
                 }
             }
         }
+    }
+    if options.is_reversed(flip) {
+        for triangle in indices.chunks_exact_mut(3) {
+            triangle.swap(1, 2);
+        }
+    }
 
-        let mut mesh = Mesh::new(
-            PrimitiveTopology::TriangleList,
-            RenderAssetUsages::default(),
-        );
-        debug_assert_eq!(indices.len(), physical.total_indices());
-        mesh.insert_indices(Indices::U32(indices));
-        debug_assert_eq!(positions.len(), physical.total_vertices());
-        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
-        debug_assert_eq!(normals.len(), physical.total_vertices());
-        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
-        #[cfg(feature = "uvf")]
-        if self.options.generate_uv {
-            debug_assert_eq!(uvs.len(), physical.total_vertices());
-            mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    let mut mesh = match primitives {
+        BoxPrimitives::Triangles => {
+            let omitted_indices: usize = (0..6)
+                .filter(|&face| options.is_face_omitted(face))
+                .map(|face| physical.flat_face_indices(face))
+                .sum();
+            // Sharp edges and corners leave out their degenerate triangles
+            debug_assert!(indices.len() <= physical.total_indices() - omitted_indices);
+            Mesh::new(
+                PrimitiveTopology::TriangleList,
+                RenderAssetUsages::default(),
+            )
         }
-        #[cfg(feature = "uvf")]
-        if self.options.generate_face {
-            debug_assert_eq!(faces.len(), physical.total_vertices());
-            mesh.insert_attribute(ATTRIBUTE_FACE, faces);
+        BoxPrimitives::Lines { isolines } => {
+            indices = quad_lines(&quads, &positions, isolines);
+            Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::default())
         }
-        mesh
+    };
+    mesh.insert_indices(Indices::U32(indices));
+    debug_assert_eq!(positions.len(), physical.total_vertices());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    debug_assert_eq!(normals.len(), physical.total_vertices());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    #[cfg(feature = "uvf")]
+    if options.generate_uv {
+        debug_assert_eq!(uvs.len(), physical.total_vertices());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
     }
+    #[cfg(feature = "uvf")]
+    if options.generate_face {
+        debug_assert_eq!(faces.len(), physical.total_vertices());
+        mesh.insert_attribute(ATTRIBUTE_FACE, faces);
+    }
+    mesh
 }
 
-impl RoundedBoxMeshBuilder {
-    /// Sets the number of subdivisions.
-    pub const fn with_subdivisions(self, subdivisions: usize) -> Self {
-        RoundedBoxMeshBuilder {
-            subdivisions,
+/// Displaces the flat regions of the faces of a box outwards by up to `bulge` at their centres.
+///
+/// Each region is displaced by the product of a bump across each of its axes, whose slope is
+/// zero where it meets the edges, so that its normals blend into theirs.
+fn bulge_faces(mesh: &mut Mesh, edges: &BoxEdges, bulge: f32) {
+    let Some(normals) = mesh
+        .attribute(Mesh::ATTRIBUTE_NORMAL)
+        .and_then(VertexAttributeValues::as_float3)
+        .map(<[_]>::to_vec)
+    else {
+        return;
+    };
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return;
+    };
+    let bump = |t: f32| 16.0 * t * t * (1.0 - t) * (1.0 - t);
+    let bump_slope = |t: f32| 32.0 * t * (1.0 - t) * (1.0 - 2.0 * t);
+
+    let mut bulged_normals = normals.clone();
+    for ((position, normal), bulged_normal) in
+        positions.iter_mut().zip(&normals).zip(&mut bulged_normals)
+    {
+        let (pos, normal) = (Vec3::from(*position), Vec3::from(*normal));
+
+        // Only the vertices of flat regions have the normal of a face and lie in its plane
+        let axis = (0..3)
+            .max_by(|&a, &b| normal[a].abs().total_cmp(&normal[b].abs()))
+            .unwrap();
+        let sign = pos[axis].signum();
+        if normal.abs()[axis] < 1.0 - 1e-6 || (pos[axis].abs() - edges.half_size[axis]).abs() > 1e-5
+        {
+            continue;
+        }
+
+        // Find how far across the region the vertex lies along each of its axes
+        let across = [(axis + 1) % 3, (axis + 2) % 3].map(|along| {
+            let extent = |side: f32| {
+                let mut corner = Vec3::ZERO;
+                corner[axis] = sign;
+                corner[along] = side;
+                edges.core_extent(3 - axis - along, corner, along)
+            };
+            let (min, max) = (extent(-1.0), extent(1.0));
+            let t = ((pos[along] + min) / (min + max)).clamp(0.0, 1.0);
+            (along, t, min + max)
+        });
+        let [(u_axis, u, u_length), (v_axis, v, v_length)] = across;
+        let mut outward = Vec3::ZERO;
+        outward[axis] = sign;
+        let mut gradient = Vec3::ZERO;
+        gradient[u_axis] = bulge * bump_slope(u) * bump(v) / u_length;
+        gradient[v_axis] = bulge * bump(u) * bump_slope(v) / v_length;
+        *position = (pos + bulge * bump(u) * bump(v) * outward).to_array();
+        let facing = normal.dot(outward).signum();
+        *bulged_normal = (facing * (outward - gradient).normalize()).to_array();
+    }
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, bulged_normals);
+}
+
+/// The cosine of the smallest angle between normals which [`split_creases`] treats as a crease.
+///
+/// This is 60°, between the 45° that neighbouring normals of a rounded edge differ by at a
+/// single subdivision and the 90° at which the faces meet along a sharp edge.
+const CREASE_COS: f32 = 0.5;
+
+/// Gives the surfaces on either side of a crease their own vertices along it.
+///
+/// The vertices along a crease carry the normal of one side of it. The triangles whose normals
+/// differ by more than 60° from a vertex are given a copy of it instead, shared by those whose
+/// normals agree, with their average normal.
+fn split_creases(mesh: &mut Mesh, clockwise: bool) {
+    let (Some(Indices::U32(indices)), Some(positions), Some(normals)) = (
+        mesh.indices(),
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3),
+        mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+            .and_then(VertexAttributeValues::as_float3),
+    ) else {
+        return;
+    };
+    let mut indices = indices.clone();
+
+    // Collect the corners of triangles which lie across a crease from their vertex
+    let mut crossings: BTreeMap<u32, Vec<(usize, Vec3)>> = BTreeMap::new();
+    for (triangle, corners) in indices.chunks_exact(3).zip((0..).step_by(3)) {
+        let [a, b, c] = [0, 1, 2].map(|i| Vec3::from(positions[triangle[i] as usize]));
+        let Some(normal) = (b - a).cross(c - a).try_normalize() else {
+            continue;
+        };
+        let normal = if clockwise { -normal } else { normal };
+        for (corner, &index) in (corners..).zip(triangle) {
+            if Vec3::from(normals[index as usize]).dot(normal) < CREASE_COS {
+                crossings.entry(index).or_default().push((corner, normal));
+            }
+        }
+    }
+
+    // Group the corners at each vertex by the side of the crease they lie on
+    let mut copies: Vec<(usize, [f32; 3])> = Vec::new();
+    for (index, corners) in crossings {
+        let mut sides: Vec<(Vec3, Vec<usize>)> = Vec::new();
+        for (corner, normal) in corners {
+            match sides
+                .iter_mut()
+                .find(|(sum, _)| sum.normalize().dot(normal) > CREASE_COS)
+            {
+                Some((sum, side)) => {
+                    *sum += normal;
+                    side.push(corner);
+                }
+                None => sides.push((normal, vec![corner])),
+            }
+        }
+        for (sum, side) in sides {
+            let copy = (positions.len() + copies.len()) as u32;
+            for corner in side {
+                indices[corner] = copy;
+            }
+            copies.push((index as usize, sum.normalize().to_array()));
+        }
+    }
+
+    fn extend_copies<T: Copy>(values: &mut Vec<T>, copies: &[(usize, [f32; 3])]) {
+        for &(source, _) in copies {
+            values.push(values[source]);
+        }
+    }
+    macro_rules! extend_all {
+        ($values:expr, $($format:ident),*) => {
+            match $values {
+                $(VertexAttributeValues::$format(values) => extend_copies(values, &copies),)*
+            }
+        };
+    }
+    for (attribute, values) in mesh.attributes_mut() {
+        if let VertexAttributeValues::Float32x3(values) = values {
+            if attribute.id == Mesh::ATTRIBUTE_NORMAL.id {
+                values.extend(copies.iter().map(|&(_, normal)| normal));
+                continue;
+            }
+        }
+        extend_all!(
+            values, Float32, Sint32, Uint32, Float32x2, Sint32x2, Uint32x2, Float32x3, Sint32x3,
+            Uint32x3, Float32x4, Sint32x4, Uint32x4, Sint16x2, Snorm16x2, Uint16x2, Unorm16x2,
+            Sint16x4, Snorm16x4, Uint16x4, Unorm16x4, Sint8x2, Snorm8x2, Uint8x2, Unorm8x2,
+            Sint8x4, Snorm8x4, Uint8x4, Unorm8x4
+        );
+    }
+    mesh.insert_indices(Indices::U32(indices));
+}
+
+/// The axis through the top and bottom faces of a [`RoundedBox`], where the corners have their
+/// poles and the stacks are counted.
+///
+/// The faces are numbered for [`ATTRIBUTE_FACE`] as follows, with the sizes, radii and segments
+/// of the box still given along the X, Y and Z axes:
+///
+/// | Pole axis | 0  | 1  | 2  | 3  | 4  | 5  |
+/// |-----------|----|----|----|----|----|----|
+/// | X         | +X | +Z | -Y | -Z | +Y | -X |
+/// | Y         | +Y | +X | -Z | -X | +Z | -Y |
+/// | Z         | +Z | +Y | -X | -Y | +X | -Z |
+///
+/// The axes are permuted cyclically, so the winding of the triangles is unchanged.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PoleAxis {
+    /// Poles on ±X.
+    X,
+    /// Poles on ±Y, so that face 0 is the top in Bevy's Y-up convention.
+    Y,
+    /// Poles on ±Z.
+    #[default]
+    Z,
+}
+
+impl PoleAxis {
+    /// Permutes the components along X, Y and Z into those of a box with poles on Z.
+    fn onto_z<T>(self, [x, y, z]: [T; 3]) -> [T; 3] {
+        match self {
+            PoleAxis::X => [y, z, x],
+            PoleAxis::Y => [z, x, y],
+            PoleAxis::Z => [x, y, z],
+        }
+    }
+
+    /// Permutes the components of a box with poles on Z back onto X, Y and Z.
+    fn onto_axis<T>(self, [x, y, z]: [T; 3]) -> [T; 3] {
+        match self {
+            PoleAxis::X => [z, x, y],
+            PoleAxis::Y => [y, z, x],
+            PoleAxis::Z => [x, y, z],
+        }
+    }
+}
+
+/// How the corners and edges of a [`RoundedBox`] are divided into triangles.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CornerTessellation {
+    /// Each corner is an octant of a UV sphere, whose poles meet the top and bottom faces in
+    /// fans of triangles.
+    #[default]
+    UvSphere,
+    /// Each face is a grid on a subdivided cube, so that the corners are divided into
+    /// quadrilaterals of similar size and no face has a pole.
+    ///
+    /// The subdivisions are rounded up to an even number, and the stack subdivisions are not
+    /// used.
+    QuadSphere,
+}
+
+/// The shape of the cross-section of the edges and corners of a [`RoundedBox`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum CornerProfile {
+    /// A circular arc.
+    #[default]
+    Circular,
+    /// A superellipse with the given exponent, which must be at least 1.
+    ///
+    /// An exponent of 2 is circular, while larger exponents give flatter, "squircle" corners
+    /// with continuously varying curvature.
+    Superellipse(f32),
+    /// A flat bevel, with flat-shaded normals.
+    ///
+    /// The radius is the distance the bevel cuts into each face. Each edge is a single sector
+    /// and stack, overriding the subdivisions, and always uses
+    /// [`CornerTessellation::UvSphere`], so that each corner is a single triangle. The mesh is
+    /// not indexed, so that each triangle has its own vertices.
+    Chamfer,
+    /// A user-defined curve, such as an ogee or a moulding.
+    Curve(ProfileCurve),
+    /// A staircase of the given number of axis-aligned steps, with flat-shaded normals, whose
+    /// inner corners lie on a circular arc.
+    ///
+    /// Each step is a riser and a tread, so the corners have twice as many sectors and stacks
+    /// as there are steps, overriding the subdivisions, and always use
+    /// [`CornerTessellation::UvSphere`]. Each step lies within one face, except for the middle
+    /// step of an odd number, which is divided at the diagonal of the edge. As for
+    /// [`Self::Chamfer`], the mesh is not indexed.
+    Stepped(usize),
+}
+
+impl CornerProfile {
+    /// Returns the power the cosine and sine of the angle are raised to.
+    fn exponent(&self) -> f32 {
+        match *self {
+            CornerProfile::Circular | CornerProfile::Curve(_) | CornerProfile::Stepped(_) => 1.0,
+            CornerProfile::Chamfer => 2.0,
+            CornerProfile::Superellipse(exponent) => {
+                debug_assert!(exponent >= 1.0);
+                2.0 / exponent
+            }
+        }
+    }
+
+    /// Returns the point on the profile which corresponds to `unit` on the unit circle, and
+    /// the tangent in the direction of increasing angle.
+    ///
+    /// The tangent is scaled to remain finite at either end of the profile.
+    fn point(&self, unit: Vec2) -> (Vec2, Vec2) {
+        match *self {
+            CornerProfile::Curve(ref curve) => {
+                return curve.point(unit.y.atan2(unit.x) / FRAC_PI_2)
+            }
+            CornerProfile::Stepped(steps) => {
+                debug_assert!(steps > 0);
+                // Snap to the corners of the steps, which the sectors and stacks fall on
+                let corners = 2 * steps;
+                let position = unit.y.atan2(unit.x) / FRAC_PI_2 * corners as f32;
+                let position = if (position - position.round()).abs() < 1e-3 {
+                    position.round()
+                } else {
+                    position
+                };
+                let i = (position.max(0.0) as usize).min(corners - 1);
+                let [start, end] = [i, i + 1].map(|corner| Self::step_corner(steps, corner));
+                let tangent = (end - start) * corners as f32 / FRAC_PI_2;
+                return (start.lerp(end, position - i as f32), tangent);
+            }
+            _ => {}
+        }
+        let exponent = self.exponent();
+        let tangent = Vec2::new(-unit.y.powf(2.0 - exponent), unit.x.powf(2.0 - exponent));
+        (unit.powf(exponent), tangent)
+    }
+
+    /// Returns a corner of a staircase of `steps` steps, counted from where it leaves the first
+    /// face. The even corners lie on the arc, at equal angles, and the odd corners outside it.
+    fn step_corner(steps: usize, corner: usize) -> Vec2 {
+        if corner == 2 * steps {
+            return Vec2::Y;
+        }
+        let angle = |step: usize| step as f32 * FRAC_PI_2 / steps as f32;
+        let riser = corner / 2;
+        let tread = riser + corner % 2;
+        Vec2::new(angle(riser).cos(), angle(tread).sin())
+    }
+
+    /// Returns whether the profile is made of flat facets, which have flat-shaded normals.
+    fn is_faceted(&self) -> bool {
+        matches!(self, CornerProfile::Chamfer | CornerProfile::Stepped(_))
+    }
+
+    /// Returns the derivative of the squared cosine of the angle, scaled as for the tangent
+    /// returned by [`Self::point`].
+    fn weight_derivative(&self, unit: Vec2) -> f32 {
+        let exponent = self.exponent();
+        -2.0 / exponent * (unit.x * unit.y).powf(2.0 - exponent)
+    }
+}
+
+/// A user-defined cross-section for [`CornerProfile::Curve`], sampled from a [`Curve`].
+///
+/// The curve is expressed relative to the centre of the arc a [`CornerProfile::Circular`] edge
+/// would have, in units of the radius, with X pointing out of one face and Y out of the other.
+/// Over its domain, it should run from (1, 0), where it leaves the first face, to (0, 1), where
+/// it meets the second.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfileCurve {
+    points: Arc<[Vec2]>,
+}
+
+impl ProfileCurve {
+    /// The number of segments the curve is sampled in, between which it is interpolated.
+    pub const SEGMENTS: usize = 64;
+
+    /// Samples a profile from a curve at evenly spaced parameters, or returns `None` if the
+    /// domain of the curve is unbounded.
+    pub fn new(curve: impl Curve<Vec2>) -> Option<Self> {
+        let domain = curve.domain();
+        if !domain.is_bounded() {
+            return None;
+        }
+        let points = (0..=Self::SEGMENTS)
+            .map(|i| {
+                let fraction = i as f32 / Self::SEGMENTS as f32;
+                curve.sample_clamped(domain.start() + fraction * domain.length())
+            })
+            .collect();
+        Some(Self { points })
+    }
+
+    /// Returns the tangent at a sample with respect to its index, using second-order
+    /// differences so that the normals at either end are accurate.
+    fn sample_tangent(&self, i: usize) -> Vec2 {
+        let p = &*self.points;
+        match i {
+            0 => (-3.0 * p[0] + 4.0 * p[1] - p[2]) / 2.0,
+            Self::SEGMENTS => (3.0 * p[i] - 4.0 * p[i - 1] + p[i - 2]) / 2.0,
+            _ => (p[i + 1] - p[i - 1]) / 2.0,
+        }
+    }
+
+    /// Returns the point at `fraction` of the domain and the tangent with respect to the angle
+    /// of the corresponding circular arc.
+    fn point(&self, fraction: f32) -> (Vec2, Vec2) {
+        let position = fraction.clamp(0.0, 1.0) * Self::SEGMENTS as f32;
+        let i = (position as usize).min(Self::SEGMENTS - 1);
+        let t = position - i as f32;
+        // Exact at either end of each segment
+        let point = self.points[i] * (1.0 - t) + self.points[i + 1] * t;
+        let tangent = self.sample_tangent(i) * (1.0 - t) + self.sample_tangent(i + 1) * t;
+        (point, tangent * Self::SEGMENTS as f32 / FRAC_PI_2)
+    }
+}
+
+/// The index of the box face the vertex belongs to.
+///
+/// The +Z and -Z faces are numbered 0 and 5. The faces on the sides are numbered 1 to 4. The
+/// faces of the inner surface of a shell are numbered 6 to 11 in the same order. See
+/// [`PoleAxis`] for the numbering with poles on other axes.
+///
+/// [`RoundedCylinder`] numbers its top, side and bottom 0, 1 and 2. [`RoundedPrism`] numbers its
+/// top 0, its sides 1 to n and its bottom n + 1.
+pub const ATTRIBUTE_FACE: MeshVertexAttribute =
+    MeshVertexAttribute::new("Face", 1554371710, VertexFormat::Uint32);
+
+/// Options for generating the mesh of a [`RoundedBox`]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct RoundedBoxMeshOptions {
+    #[cfg(feature = "uvf")]
+    generate_uv: bool,
+    #[cfg(feature = "uvf")]
+    generate_face: bool,
+    inverted: bool,
+    clockwise: bool,
+    omitted_faces: u64,
+}
+
+impl RoundedBoxMeshOptions {
+    /// Default mesh options.
+    pub const DEFAULT: Self = RoundedBoxMeshOptions {
+        #[cfg(feature = "uvf")]
+        generate_uv: false,
+        #[cfg(feature = "uvf")]
+        generate_face: false,
+        inverted: false,
+        clockwise: false,
+        omitted_faces: 0,
+    };
+
+    /// Enable generating [`Mesh::ATTRIBUTE_UV_0`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub const fn with_uv(self) -> Self {
+        RoundedBoxMeshOptions {
+            generate_uv: true,
+            ..self
+        }
+    }
+
+    /// Enable generating [`ATTRIBUTE_FACE`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub const fn with_face(self) -> Self {
+        RoundedBoxMeshOptions {
+            generate_face: true,
             ..self
         }
     }
 
+    /// Turn the mesh inside out, for viewing from inside.
+    ///
+    /// The winding of the triangles is reversed, the normals point inwards and the texture
+    /// coordinates are mirrored so that textures read correctly from inside.
+    pub const fn with_inverted(mut self) -> Self {
+        self.inverted = true;
+        self
+    }
+
+    /// Wind the triangles clockwise when seen from the side the normals point to, for
+    /// renderers or left-handed conventions whose front faces are clockwise.
+    ///
+    /// The normals and texture coordinates are unchanged.
+    pub const fn with_clockwise(mut self) -> Self {
+        self.clockwise = true;
+        self
+    }
+
+    /// Omit the flat quad of a face, numbered as for [`ATTRIBUTE_FACE`], leaving an opening
+    /// bounded by the rounded edges around it. The face must be numbered less than 64.
+    pub const fn without_face(mut self, face: u32) -> Self {
+        assert!(face < u64::BITS, "invalid face");
+        self.omitted_faces |= 1 << face;
+        self
+    }
+
+    fn is_face_omitted(&self, face: u32) -> bool {
+        face < u64::BITS && self.omitted_faces & (1 << face) != 0
+    }
+
+    fn is_generate_uv(&self) -> bool {
+        #[cfg(feature = "uvf")]
+        {
+            self.generate_uv
+        }
+        #[cfg(not(feature = "uvf"))]
+        {
+            false
+        }
+    }
+
+    fn is_generate_face(&self) -> bool {
+        #[cfg(feature = "uvf")]
+        {
+            self.generate_face
+        }
+        #[cfg(not(feature = "uvf"))]
+        {
+            false
+        }
+    }
+
+    /// Returns whether the winding of the triangles is reversed, given whether the surface is
+    /// turned inside out.
+    fn is_reversed(&self, flip: bool) -> bool {
+        flip != self.clockwise
+    }
+
+    fn is_split_faces(&self) -> bool {
+        self.is_generate_uv() || self.is_generate_face()
+    }
+}
+
+/// A rounded box.
+#[derive(Copy, Clone, Debug)]
+pub struct RoundedBox {
+    /// The dimensions of the box.
+    pub size: Vec3,
+    /// The radius of the corners and edges along each axis.
+    ///
+    /// Unequal radii give the corners an ellipsoidal shape and the edges an elliptical
+    /// cross-section. Negative radii curve the edges inwards, as coves, which meet the faces
+    /// at creases.
+    pub radius: Vec3,
+}
+
+impl RoundedBox {
+    /// Creates a rounded box with the same radius along each axis.
+    pub const fn new(size: Vec3, radius: f32) -> Self {
+        Self {
+            size,
+            radius: Vec3::splat(radius),
+        }
+    }
+}
+
+impl Default for RoundedBox {
+    fn default() -> Self {
+        Self::new(Vec3::ONE, 0.1)
+    }
+}
+
+impl Meshable for RoundedBox {
+    type Output = RoundedBoxMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        RoundedBoxMeshBuilder {
+            rounded_box: *self,
+            subdivisions: 4,
+            stack_subdivisions: None,
+            segments: UVec3::ONE,
+            bulge: 0.0,
+            edge_radii: None,
+            profile: CornerProfile::Circular,
+            tessellation: CornerTessellation::UvSphere,
+            pole_axis: PoleAxis::Z,
+            shell_thickness: None,
+            options: RoundedBoxMeshOptions::DEFAULT,
+        }
+    }
+}
+
+impl From<RoundedBox> for Mesh {
+    fn from(value: RoundedBox) -> Self {
+        value.mesh().build()
+    }
+}
+
+/// The radius of each of the twelve edges of a [`RoundedBox`].
+///
+/// The four edges parallel to each axis are ordered by the quadrant they lie in on the plane
+/// of the other two axes, taken in cyclic order (YZ for X, ZX for Y and XY for Z). The
+/// quadrants are ordered anticlockwise, starting from where both coordinates are positive.
+///
+/// A radius of zero gives a sharp edge, and a negative radius a cove. Where coves and rounded
+/// edges meet at a corner, the corner follows the edge parallel to the pole axis around it and
+/// the other two edges over it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct EdgeRadii {
+    /// The radii of the edges parallel to the X axis.
+    pub x: [f32; 4],
+    /// The radii of the edges parallel to the Y axis.
+    pub y: [f32; 4],
+    /// The radii of the edges parallel to the Z axis.
+    pub z: [f32; 4],
+}
+
+impl EdgeRadii {
+    /// Creates edge radii with the same radius for every edge.
+    pub const fn splat(radius: f32) -> Self {
+        EdgeRadii {
+            x: [radius; 4],
+            y: [radius; 4],
+            z: [radius; 4],
+        }
+    }
+
+    /// Sets the radius of the four edges around a face, numbered as for [`ATTRIBUTE_FACE`] with
+    /// poles on Z.
+    pub const fn with_face(mut self, face: u32, radius: f32) -> Self {
+        let (axes, quarters) = match face {
+            0 => ([0, 1], [[0, 1], [0, 3]]),
+            1 => ([0, 2], [[0, 3], [0, 1]]),
+            2 => ([1, 2], [[2, 3], [1, 2]]),
+            3 => ([0, 2], [[1, 2], [2, 3]]),
+            4 => ([1, 2], [[0, 1], [0, 3]]),
+            5 => ([0, 1], [[2, 3], [1, 2]]),
+            _ => panic!("invalid face"),
+        };
+        let mut i = 0;
+        while i < 2 {
+            let edges = match axes[i] {
+                0 => &mut self.x,
+                1 => &mut self.y,
+                _ => &mut self.z,
+            };
+            edges[quarters[i][0]] = radius;
+            edges[quarters[i][1]] = radius;
+            i += 1;
+        }
+        self
+    }
+}
+
+/// A builder used for creating a [`Mesh`] with a [`RoundedBox`] shape.
+#[derive(Clone, Debug)]
+pub struct RoundedBoxMeshBuilder {
+    /// The [`RoundedBox`] shape.
+    pub rounded_box: RoundedBox,
+    /// The number of sectors in each corner, and stacks unless [`Self::stack_subdivisions`] is
+    /// set.
+    pub subdivisions: usize,
+    /// The number of stacks in each corner, overriding [`Self::subdivisions`] if set.
+    ///
+    /// The sectors sweep around the vertical edges and the stacks across the horizontal ones.
+    pub stack_subdivisions: Option<usize>,
+    /// The number of segments the flat regions of the faces are divided into along each axis.
+    pub segments: UVec3,
+    /// The distance the centres of the faces bulge outwards, like a pillow.
+    ///
+    /// The bulge falls smoothly to nothing where the faces meet the edges, which stay in place,
+    /// so it only shows on faces divided into more than 1 of [`Self::segments`] along both of
+    /// their axes, and other faces stay flat. The mesh then extends beyond the size of the box by
+    /// up to the bulge. Faceted profiles, [`CornerProfile::Chamfer`] and
+    /// [`CornerProfile::Stepped`], are not bulged.
+    pub bulge: f32,
+    /// The radius of each edge, overriding [`RoundedBox::radius`] if set.
+    pub edge_radii: Option<EdgeRadii>,
+    /// The shape of the cross-section of the edges and corners.
+    pub profile: CornerProfile,
+    /// How the corners and edges are divided into triangles.
+    pub tessellation: CornerTessellation,
+    /// The axis through the top and bottom faces.
+    pub pole_axis: PoleAxis,
+    /// The thickness of the wall of a hollow shell, if set.
+    ///
+    /// The mesh then has an inner surface, inset by the thickness and facing inwards.
+    pub shell_thickness: Option<f32>,
+    /// Mesh generation options.
+    pub options: RoundedBoxMeshOptions,
+}
+
+impl Default for RoundedBoxMeshBuilder {
+    fn default() -> Self {
+        RoundedBox::default().mesh()
+    }
+}
+
+impl MeshBuilder for RoundedBoxMeshBuilder {
+    fn build(&self) -> Mesh {
+        self.build_primitives(BoxPrimitives::Triangles)
+    }
+}
+
+impl RoundedBoxMeshBuilder {
+    /// Builds the box, or its wireframe for [`BoxPrimitives::Lines`].
+    fn build_primitives(&self, primitives: BoxPrimitives) -> Mesh {
+        let edges = self.edges();
+        let mut mesh = self.build_surface(&edges, false, primitives);
+        if let Some(thickness) = self.shell_thickness {
+            debug_assert!(thickness > 0.0 && thickness < edges.half_size.min_element());
+            let inner = self.build_surface(&edges.inset(thickness), true, primitives);
+            mesh.merge(&inner).unwrap();
+        }
+        let triangles = primitives == BoxPrimitives::Triangles;
+        if triangles && edges.has_crease() && !self.profile.is_faceted() {
+            split_creases(&mut mesh, self.options.clockwise);
+        }
+        if triangles && self.profile.is_faceted() {
+            mesh.duplicate_vertices();
+            mesh.compute_flat_normals();
+            if self.options.clockwise {
+                if let Some(VertexAttributeValues::Float32x3(normals)) =
+                    mesh.attribute_mut(Mesh::ATTRIBUTE_NORMAL)
+                {
+                    for normal in normals {
+                        *normal = normal.map(|c| -c);
+                    }
+                }
+            }
+        }
+
+        // The surfaces are built with poles on Z
+        for attribute in [Mesh::ATTRIBUTE_POSITION, Mesh::ATTRIBUTE_NORMAL] {
+            if let Some(VertexAttributeValues::Float32x3(values)) = mesh.attribute_mut(attribute) {
+                for value in values {
+                    *value = self.pole_axis.onto_axis(*value);
+                }
+            }
+        }
+        mesh
+    }
+
+    /// Builds the outer surface of the box, or the inner surface of a shell, which faces the
+    /// opposite way and numbers its faces from 6.
+    fn build_surface(&self, edges: &BoxEdges, inner: bool, primitives: BoxPrimitives) -> Mesh {
+        let vertex = |corner, dir| {
+            let (inset, normal) = edges.corner_point(corner, dir, &self.profile);
+            ((edges.half_size - inset) * corner, normal * corner)
+        };
+        let segments = UVec3::from_array(self.pole_axis.onto_z(self.segments.to_array()));
+        let (subdivisions, stack_subdivisions, tessellation) = match self.profile {
+            CornerProfile::Chamfer => (1, 1, CornerTessellation::UvSphere),
+            CornerProfile::Stepped(steps) => (2 * steps, 2 * steps, CornerTessellation::UvSphere),
+            _ => (
+                self.subdivisions,
+                self.stack_subdivisions.unwrap_or(self.subdivisions),
+                self.tessellation,
+            ),
+        };
+        let mut mesh = match tessellation {
+            CornerTessellation::UvSphere => build_box_topology(
+                subdivisions,
+                stack_subdivisions,
+                segments,
+                &self.options,
+                inner,
+                primitives,
+                vertex,
+                |_| edges,
+            ),
+            CornerTessellation::QuadSphere => build_quad_sphere_topology(
+                subdivisions,
+                segments,
+                &self.options,
+                inner,
+                primitives,
+                vertex,
+                |_| edges,
+            ),
+        };
+        // The flat normals of faceted profiles are computed afterwards from the positions, which
+        // would show the bulge as facets of its own
+        if self.bulge != 0.0 && !self.profile.is_faceted() {
+            bulge_faces(&mut mesh, edges, self.bulge);
+        }
+        mesh
+    }
+
+    /// Returns the edges of the box with its poles on Z.
+    fn edges(&self) -> BoxEdges {
+        let onto_z = |v: Vec3| Vec3::from_array(self.pole_axis.onto_z(v.to_array()));
+        let radii = match self.edge_radii {
+            Some(edge_radii) => self
+                .pole_axis
+                .onto_z([edge_radii.x, edge_radii.y, edge_radii.z])
+                .map(|axis| axis.map(Vec3::splat)),
+            None => [[onto_z(self.rounded_box.radius); 4]; 3],
+        };
+        BoxEdges {
+            half_size: onto_z(self.rounded_box.size / 2.0),
+            radii,
+        }
+    }
+
+    /// Sets the number of subdivisions.
+    pub const fn with_subdivisions(mut self, subdivisions: usize) -> Self {
+        self.subdivisions = subdivisions;
+        self
+    }
+
+    /// Sets the number of stacks in each corner, overriding the number of subdivisions.
+    pub const fn with_stack_subdivisions(mut self, stack_subdivisions: usize) -> Self {
+        self.stack_subdivisions = Some(stack_subdivisions);
+        self
+    }
+
+    /// Sets the number of segments the flat regions of the faces are divided into along each
+    /// axis. A count of 0 is treated as 1.
+    pub const fn with_segments(mut self, segments: UVec3) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Sets the distance the centres of the faces bulge outwards, which only shows on faces
+    /// divided into more than 1 segment along both of their axes.
+    pub const fn with_bulge(mut self, bulge: f32) -> Self {
+        self.bulge = bulge;
+        self
+    }
+
+    /// Sets the radius of each edge, overriding [`RoundedBox::radius`].
+    ///
+    /// The radii apply as given along both axes across each edge, ignoring any per-axis radii
+    /// from [`RoundedBox::radius`]. An edge with a radius of zero is sharp.
+    pub const fn with_edge_radii(mut self, edge_radii: EdgeRadii) -> Self {
+        self.edge_radii = Some(edge_radii);
+        self
+    }
+
+    /// Sets the shape of the cross-section of the edges and corners.
+    pub fn with_profile(mut self, profile: CornerProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Sets the axis through the top and bottom faces.
+    pub const fn with_pole_axis(mut self, pole_axis: PoleAxis) -> Self {
+        self.pole_axis = pole_axis;
+        self
+    }
+
+    /// Sets how the corners and edges are divided into triangles.
+    pub const fn with_tessellation(mut self, tessellation: CornerTessellation) -> Self {
+        self.tessellation = tessellation;
+        self
+    }
+
+    /// Makes the box a hollow shell with the given wall thickness.
+    pub const fn with_shell(mut self, thickness: f32) -> Self {
+        self.shell_thickness = Some(thickness);
+        self
+    }
+
     /// Sets the mesh generation options.
-    pub const fn with_options(self, options: RoundedBoxMeshOptions) -> Self {
-        RoundedBoxMeshBuilder { options, ..self }
+    pub const fn with_options(mut self, options: RoundedBoxMeshOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Turn the mesh inside out, for viewing from inside.
+    pub const fn with_inverted(mut self) -> Self {
+        self.options = self.options.with_inverted();
+        self
+    }
+
+    /// Omit the flat quad of a face, numbered as for [`ATTRIBUTE_FACE`].
+    pub const fn without_face(mut self, face: u32) -> Self {
+        self.options = self.options.without_face(face);
+        self
     }
 
     /// Enable generating [`Mesh::ATTRIBUTE_UV_0`]. Requires `uvf` feature.
@@ -546,20 +1762,52 @@ impl RoundedBoxMeshBuilder {
         self.options = self.options.with_face();
         self
     }
+
+    /// Creates a builder for a wireframe of the box with the same tessellation.
+    pub const fn wireframe(self) -> RoundedBoxWireframeMeshBuilder {
+        RoundedBoxWireframeMeshBuilder {
+            builder: self,
+            isolines: false,
+        }
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::collections::HashSet;
+/// A builder used for creating a [`Mesh`] with [`PrimitiveTopology::LineList`] of the outlines of
+/// a [`RoundedBox`], such as for editor overlays.
+///
+/// The lines run around the flat regions of the faces, where they meet the rounded edges, and
+/// along the sharp edges and the rims of omitted faces. The vertices are those of the mesh of
+/// [`Self::builder`], so the lines follow its surface exactly.
+#[derive(Clone, Debug, Default)]
+pub struct RoundedBoxWireframeMeshBuilder {
+    /// The builder of the solid box, whose tessellation the lines follow.
+    pub builder: RoundedBoxMeshBuilder,
+    /// Whether to draw lines along the sectors and stacks of the rounded edges and corners too.
+    pub isolines: bool,
+}
 
-    #[cfg(feature = "uvf")]
-    const MESH_OPTIONS: [RoundedBoxMeshOptions; 2] = [
-        RoundedBoxMeshOptions::DEFAULT,
-        RoundedBoxMeshOptions::DEFAULT.with_uv().with_face(),
-    ];
-    #[cfg(not(feature = "uvf"))]
-    const MESH_OPTIONS: [RoundedBoxMeshOptions; 1] = [RoundedBoxMeshOptions::DEFAULT];
+impl MeshBuilder for RoundedBoxWireframeMeshBuilder {
+    fn build(&self) -> Mesh {
+        self.builder.build_primitives(BoxPrimitives::Lines {
+            isolines: self.isolines,
+        })
+    }
+}
+
+impl RoundedBoxWireframeMeshBuilder {
+    /// Draw lines across the rounded edges and corners along each sector and stack.
+    pub const fn with_isolines(mut self) -> Self {
+        self.isolines = true;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use bevy::render::mesh::VertexAttributeValues;
+    use std::{collections::HashSet, f32::consts::TAU};
 
     #[test]
     fn test_create_mesh() {
@@ -569,7 +1817,7 @@ mod tests {
                 println!("subdivions={} options={:?}", subdivisions, options);
                 let mesh = RoundedBox {
                     size: Vec3::new(1.0, 1.0, 1.0),
-                    radius: 0.1,
+                    radius: Vec3::splat(0.1),
                 }
                 .mesh()
                 .with_subdivisions(subdivisions)
@@ -587,44 +1835,1125 @@ mod tests {
         }
     }
 
-    fn assert_no_degenerates(mesh: &Mesh) {
-        let pos = mesh
-            .attribute(Mesh::ATTRIBUTE_POSITION)
-            .unwrap()
-            .as_float3()
-            .unwrap();
-        let indices = mesh.indices().unwrap();
-        let mut it = indices.iter();
-        while let (Some(a), Some(b), Some(c)) = (it.next(), it.next(), it.next()) {
-            assert_ne!(pos[a], pos[b]);
-            assert_ne!(pos[b], pos[c]);
-            assert_ne!(pos[c], pos[a]);
+    #[test]
+    fn test_segments() {
+        let rounded_box = RoundedBox::new(Vec3::new(2.0, 3.0, 4.0), 0.5);
+        for segments in [UVec3::ONE, UVec3::new(2, 3, 4), UVec3::new(5, 1, 2)] {
+            for subdivisions in 1..=4 {
+                for options in MESH_OPTIONS {
+                    println!(
+                        "segments={} subdivisions={} options={:?}",
+                        segments, subdivisions, options
+                    );
+                    let builder = rounded_box
+                        .mesh()
+                        .with_segments(segments)
+                        .with_subdivisions(subdivisions)
+                        .with_options(options);
+                    let mesh = builder.build();
+                    assert_no_degenerates(&mesh);
+                    assert_no_duplicates(&mesh);
+                    assert_bounds(&mesh, rounded_box.size);
+                    assert_unit_normals(&mesh);
+                    assert_outward_normals(&mesh);
+                    assert_closed(&mesh);
+
+                    // Each flat region is a grid of quads
+                    let pos = vertex_positions(&mesh);
+                    let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+                    let on_top = |i: usize| (pos[i][2] - 2.0).abs() < 1e-5;
+                    let on_side = |i: usize| (pos[i][0] - 1.0).abs() < 1e-5;
+                    let top = indices
+                        .chunks_exact(3)
+                        .filter(|triangle| triangle.iter().all(|&i| on_top(i)))
+                        .count();
+                    let side = indices
+                        .chunks_exact(3)
+                        .filter(|triangle| triangle.iter().all(|&i| on_side(i)))
+                        .count();
+                    assert_eq!(top as u32, 2 * segments.x * segments.y);
+                    assert_eq!(side as u32, 2 * segments.y * segments.z);
+
+                    // Texture coordinates remain continuous across the top
+                    if let Some(VertexAttributeValues::Float32x2(uvs)) =
+                        mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+                    {
+                        let rounded = 0.125 * TAU * 0.5;
+                        let half_extent = Vec2::new(0.5, 1.0) + rounded;
+                        for (i, &[u, v]) in uvs.iter().enumerate() {
+                            if on_top(i) {
+                                let expected = 0.5 + Vec3::from(pos[i]).xy() / half_extent / 2.0;
+                                assert!((u - expected.x).abs() < 1e-5, "u={} p={:?}", u, pos[i]);
+                                assert!((v - 1.0 + expected.y).abs() < 1e-5);
+                            }
+                        }
+                    }
+
+                    for face in 0..6 {
+                        let open = builder
+                            .clone()
+                            .with_options(options.without_face(face))
+                            .build();
+                        assert_no_degenerates(&open);
+                    }
+                }
+            }
+        }
+
+        // Flat regions with no extent along an axis leave no degenerate or sliver triangles
+        for radius in [Vec3::splat(0.5), Vec3::new(0.5, 0.5, 0.2)] {
+            let rounded_box = RoundedBox {
+                size: Vec3::ONE,
+                radius,
+            };
+            for tessellation in [CornerTessellation::UvSphere, CornerTessellation::QuadSphere] {
+                for options in MESH_OPTIONS {
+                    println!(
+                        "radius={} tessellation={:?} options={:?}",
+                        radius, tessellation, options
+                    );
+                    let mesh = rounded_box
+                        .mesh()
+                        .with_segments(UVec3::splat(3))
+                        .with_tessellation(tessellation)
+                        .with_options(options)
+                        .build();
+                    assert_no_degenerates(&mesh);
+                    assert_closed(&mesh);
+                    let pos = vertex_positions(&mesh);
+                    let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+                    for triangle in indices.chunks_exact(3) {
+                        let [a, b, c] = [0, 1, 2].map(|i| Vec3::from(pos[triangle[i]]));
+                        assert!((b - a).cross(c - a).length() > 1e-6, "{} {} {}", a, b, c);
+                    }
+                }
+            }
+        }
+
+        // Zero segments are treated as one
+        for tessellation in [CornerTessellation::UvSphere, CornerTessellation::QuadSphere] {
+            let builder = rounded_box.mesh().with_tessellation(tessellation);
+            let zero = builder.clone().with_segments(UVec3::new(0, 2, 0)).build();
+            let one = builder.with_segments(UVec3::new(1, 2, 1)).build();
+            assert_eq!(
+                zero.attribute(Mesh::ATTRIBUTE_POSITION)
+                    .unwrap()
+                    .as_float3(),
+                one.attribute(Mesh::ATTRIBUTE_POSITION).unwrap().as_float3()
+            );
+            assert_eq!(zero.indices().unwrap().len(), one.indices().unwrap().len());
+        }
+    }
+
+    #[test]
+    fn test_bulge() {
+        let rounded_box = RoundedBox::new(Vec3::new(1.0, 2.0, 3.0), 0.2);
+        for options in MESH_OPTIONS {
+            for tessellation in [CornerTessellation::UvSphere, CornerTessellation::QuadSphere] {
+                println!("options={:?} tessellation={:?}", options, tessellation);
+                let builder = rounded_box
+                    .mesh()
+                    .with_segments(UVec3::new(2, 4, 6))
+                    .with_tessellation(tessellation)
+                    .with_options(options);
+                let flat = builder.build();
+                let mesh = builder.clone().with_bulge(0.1).build();
+                assert_no_degenerates(&mesh);
+                assert_no_duplicates(&mesh);
+                assert_closed(&mesh);
+                assert_bounds(&mesh, rounded_box.size + 0.2);
+                assert_unit_normals(&mesh);
+                assert_outward_normals(&mesh);
+
+                // Only the flat regions move, outwards along the normals of their faces
+                let [pos, flat_pos] = [&mesh, &flat].map(vertex_positions);
+                let flat_normals = vertex_normals(&flat);
+                assert_eq!(pos.len(), flat_pos.len());
+                for ((&p, &flat_p), &flat_n) in pos.iter().zip(flat_pos).zip(flat_normals) {
+                    let offset = Vec3::from(p) - Vec3::from(flat_p);
+                    let flat_n = Vec3::from(flat_n);
+                    if offset != Vec3::ZERO {
+                        assert!(flat_n.abs().max_element() == 1.0, "p={:?}", p);
+                        assert!(offset.normalize().abs_diff_eq(flat_n, 1e-6), "p={:?}", p);
+                    }
+                }
+
+                let mesh = builder.clone().with_bulge(0.1).with_shell(0.1).build();
+                assert_no_degenerates(&mesh);
+                assert_closed(&mesh);
+                assert_unit_normals(&mesh);
+                assert_outward_normals(&mesh);
+
+                // Faces without interior segments stay flat
+                let flat = rounded_box.mesh().with_options(options).build();
+                let mesh = rounded_box
+                    .mesh()
+                    .with_options(options)
+                    .with_bulge(0.1)
+                    .build();
+                for (id, values) in mesh.attributes() {
+                    assert_eq!(
+                        values.get_bytes(),
+                        flat.attribute(id.id).unwrap().get_bytes()
+                    );
+                }
+
+                // Faceted profiles are not bulged
+                for profile in [CornerProfile::Chamfer, CornerProfile::Stepped(2)] {
+                    let builder = builder.clone().with_profile(profile);
+                    let flat = builder.clone().build();
+                    let mesh = builder.with_bulge(0.1).build();
+                    for (id, values) in mesh.attributes() {
+                        assert_eq!(
+                            values.get_bytes(),
+                            flat.attribute(id.id).unwrap().get_bytes()
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_stack_subdivisions() {
+        let rounded_box = RoundedBox::new(Vec3::new(4.0, 3.0, 0.5), 0.2);
+        for options in MESH_OPTIONS {
+            let uniform = rounded_box.mesh().with_options(options).build();
+            let mesh = rounded_box
+                .mesh()
+                .with_stack_subdivisions(4)
+                .with_options(options)
+                .build();
+            for (id, values) in mesh.attributes() {
+                assert_eq!(
+                    values.get_bytes(),
+                    uniform.attribute(id.id).unwrap().get_bytes()
+                );
+            }
+
+            for sectors in 1..=5 {
+                for stacks in 1..=5 {
+                    println!(
+                        "sectors={} stacks={} options={:?}",
+                        sectors, stacks, options
+                    );
+                    let mesh = rounded_box
+                        .mesh()
+                        .with_subdivisions(sectors)
+                        .with_stack_subdivisions(stacks)
+                        .with_options(options)
+                        .build();
+                    assert_no_degenerates(&mesh);
+                    assert_no_duplicates(&mesh);
+                    assert_bounds(&mesh, rounded_box.size);
+                    assert_unit_normals(&mesh);
+                    assert_outward_normals(&mesh);
+                    assert_closed(&mesh);
+
+                    // The vertical edges sweep through the sectors and the horizontal edges
+                    // through the stacks
+                    let normals = vertex_normals(&mesh);
+                    let count_angles = |axis: usize, other: usize| {
+                        let mut angles: Vec<i32> = normals
+                            .iter()
+                            .filter(|n| {
+                                n[3 - axis - other].abs() < 1e-6
+                                    && n[axis] >= 0.0
+                                    && n[other] >= 0.0
+                            })
+                            .map(|n| (n[other].atan2(n[axis]) * 1e4).round() as i32)
+                            .collect();
+                        angles.sort();
+                        angles.dedup();
+                        angles.len()
+                    };
+                    let even = |n: usize| {
+                        if options.is_split_faces() {
+                            n + n % 2
+                        } else {
+                            n
+                        }
+                    };
+                    assert_eq!(count_angles(0, 1), even(sectors) + 1);
+                    assert_eq!(count_angles(0, 2), even(stacks) + 1);
+                }
+            }
         }
     }
 
-    fn assert_no_duplicates(mesh: &Mesh) {
-        let pos = mesh
-            .attribute(Mesh::ATTRIBUTE_POSITION)
+    #[test]
+    fn test_quad_sphere() {
+        let rounded_box = RoundedBox::new(Vec3::new(2.0, 3.0, 4.0), 0.5);
+        for segments in [UVec3::ONE, UVec3::new(2, 3, 4)] {
+            for subdivisions in 1..=6 {
+                for options in MESH_OPTIONS {
+                    println!(
+                        "segments={} subdivisions={} options={:?}",
+                        segments, subdivisions, options
+                    );
+                    let builder = rounded_box
+                        .mesh()
+                        .with_tessellation(CornerTessellation::QuadSphere)
+                        .with_segments(segments)
+                        .with_subdivisions(subdivisions)
+                        .with_options(options);
+                    let mesh = builder.build();
+                    assert_no_degenerates(&mesh);
+                    assert_no_duplicates(&mesh);
+                    assert_bounds(&mesh, rounded_box.size);
+                    assert_unit_normals(&mesh);
+                    assert_outward_normals(&mesh);
+                    assert_closed(&mesh);
+
+                    // Each face is a grid of quads
+                    let half = subdivisions.div_ceil(2) as u32;
+                    let size = 2 * half + segments;
+                    let quads = 2 * (size.x * size.y + size.y * size.z + size.z * size.x);
+                    assert_eq!(mesh.indices().unwrap().len() as u32, 6 * quads);
+
+                    // Vertices belong to the face their normals are nearest to, and texture
+                    // coordinates remain continuous across the top
+                    let pos = vertex_positions(&mesh);
+                    let normals = vertex_normals(&mesh);
+                    if let Some(VertexAttributeValues::Uint32(faces)) =
+                        mesh.attribute(ATTRIBUTE_FACE)
+                    {
+                        const FACE_NORMALS: [Vec3; 6] = [
+                            Vec3::Z,
+                            Vec3::Y,
+                            Vec3::NEG_X,
+                            Vec3::NEG_Y,
+                            Vec3::X,
+                            Vec3::NEG_Z,
+                        ];
+                        for (&face, &n) in faces.iter().zip(normals) {
+                            let n = Vec3::from(n);
+                            let nearest = FACE_NORMALS
+                                .iter()
+                                .map(|face_normal| n.dot(*face_normal))
+                                .fold(f32::MIN, f32::max);
+                            assert!(n.dot(FACE_NORMALS[face as usize]) >= nearest - 1e-5);
+                        }
+                    }
+                    if let Some(VertexAttributeValues::Float32x2(uvs)) =
+                        mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+                    {
+                        let rounded = 0.125 * TAU * 0.5;
+                        let half_extent = Vec2::new(0.5, 1.0) + rounded;
+                        for (i, &[u, v]) in uvs.iter().enumerate() {
+                            assert!(
+                                (0.0..=1.0).contains(&u) && (0.0..=1.0).contains(&v),
+                                "u={} v={}",
+                                u,
+                                v
+                            );
+                            if (pos[i][2] - 2.0).abs() < 1e-5 {
+                                let expected = 0.5 + Vec3::from(pos[i]).xy() / half_extent / 2.0;
+                                assert!((u - expected.x).abs() < 1e-5, "u={} p={:?}", u, pos[i]);
+                                assert!((v - 1.0 + expected.y).abs() < 1e-5);
+                            }
+                        }
+                    }
+
+                    for face in 0..6 {
+                        let open = builder
+                            .clone()
+                            .with_options(options.without_face(face))
+                            .build();
+                        assert_no_degenerates(&open);
+                        let flat = match face {
+                            0 | 5 => segments.x * segments.y,
+                            1 | 3 => segments.x * segments.z,
+                            _ => segments.y * segments.z,
+                        };
+                        assert_eq!(open.indices().unwrap().len() as u32, 6 * (quads - flat));
+                    }
+                }
+            }
+        }
+
+        // Other options apply as for the UV sphere
+        for options in MESH_OPTIONS {
+            let builder = rounded_box
+                .mesh()
+                .with_tessellation(CornerTessellation::QuadSphere)
+                .with_options(options);
+            let mesh = builder
+                .clone()
+                .with_edge_radii(EdgeRadii {
+                    x: [0.1, 0.2, 0.3, 0.4],
+                    y: [0.5, 0.1, 0.2, 0.3],
+                    z: [0.4, 0.5, 0.1, 0.2],
+                })
+                .with_profile(CornerProfile::Superellipse(4.0))
+                .build();
+            assert_no_degenerates(&mesh);
+            assert_bounds(&mesh, rounded_box.size);
+            assert_unit_normals(&mesh);
+            assert_outward_normals(&mesh);
+            assert_closed(&mesh);
+            let mesh = builder.clone().with_shell(0.1).build();
+            assert_outward_normals(&mesh);
+            let mesh = builder.with_profile(CornerProfile::Chamfer).build();
+            assert_bounds(&mesh, rounded_box.size);
+            assert_unit_normals(&mesh);
+        }
+
+        // The corners of a sphere are divided evenly, without poles
+        let sphere = RoundedBox::new(Vec3::ONE, 0.5);
+        let mesh = sphere
+            .mesh()
+            .with_tessellation(CornerTessellation::QuadSphere)
+            .with_subdivisions(8)
+            .build();
+        let pos = vertex_positions(&mesh);
+        let areas: Vec<f32> = mesh
+            .indices()
             .unwrap()
-            .as_float3()
-            .unwrap();
-        let mut set = HashSet::<[[u32; 3]; 3]>::new();
-        let indices = mesh.indices().unwrap();
-        let mut it = indices.iter();
-        while let (Some(a), Some(b), Some(c)) = (it.next(), it.next(), it.next()) {
-            let [ax, ay, az] = pos[a];
-            let [bx, by, bz] = pos[b];
-            let [cx, cy, cz] = pos[c];
-            let mut ps = [
-                [ax.to_bits(), ay.to_bits(), az.to_bits()],
-                [bx.to_bits(), by.to_bits(), bz.to_bits()],
-                [cx.to_bits(), cy.to_bits(), cz.to_bits()],
-            ];
-            assert!(set.insert(ps));
-            ps.rotate_left(1);
-            assert!(set.insert(ps));
-            ps.rotate_left(1);
-            assert!(set.insert(ps));
+            .iter()
+            .collect::<Vec<_>>()
+            .chunks_exact(3)
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|i| Vec3::from(pos[triangle[i]]));
+                (b - a).cross(c - a).length() / 2.0
+            })
+            .collect();
+        let min = areas.iter().copied().fold(f32::MAX, f32::min);
+        let max = areas.iter().copied().fold(f32::MIN, f32::max);
+        assert!(max / min < 2.0, "min={} max={}", min, max);
+    }
+
+    #[test]
+    fn test_per_axis_radii() {
+        for options in MESH_OPTIONS {
+            println!("options={:?}", options);
+            let rounded_box = RoundedBox {
+                size: Vec3::new(1.0, 2.0, 3.0),
+                radius: Vec3::new(0.1, 0.3, 0.5),
+            };
+            let mesh = rounded_box.mesh().with_options(options).build();
+            assert_no_degenerates(&mesh);
+            assert_no_duplicates(&mesh);
+            assert_bounds(&mesh, rounded_box.size);
+            assert_unit_normals(&mesh);
+            assert_outward_normals(&mesh);
+        }
+    }
+
+    #[test]
+    fn test_edge_radii() {
+        let rounded_box = RoundedBox::new(Vec3::new(1.0, 2.0, 3.0), 0.2);
+        for options in MESH_OPTIONS {
+            println!("options={:?}", options);
+            let uniform = rounded_box.mesh().with_options(options).build();
+            let mesh = rounded_box
+                .mesh()
+                .with_edge_radii(EdgeRadii::splat(0.2))
+                .with_options(options)
+                .build();
+            assert_eq!(mesh.attributes().count(), uniform.attributes().count());
+            for (id, values) in mesh.attributes() {
+                assert_eq!(
+                    values.get_bytes(),
+                    uniform.attribute(id.id).unwrap().get_bytes()
+                );
+            }
+
+            let mesh = rounded_box
+                .mesh()
+                .with_edge_radii(EdgeRadii {
+                    x: [0.1, 0.2, 0.3, 0.4],
+                    y: [0.5, 0.1, 0.2, 0.3],
+                    z: [0.4, 0.5, 0.1, 0.2],
+                })
+                .with_options(options)
+                .build();
+            assert_no_degenerates(&mesh);
+            assert_no_duplicates(&mesh);
+            assert_bounds(&mesh, rounded_box.size);
+            assert_unit_normals(&mesh);
+            assert_outward_normals(&mesh);
+
+            // Sharp bottom edges, whose vertices are shared by both faces
+            let mesh = rounded_box
+                .mesh()
+                .with_edge_radii(EdgeRadii::splat(0.2).with_face(5, 0.0))
+                .with_options(options)
+                .build();
+            assert_no_degenerates(&mesh);
+            assert_bounds(&mesh, rounded_box.size);
+            assert_unit_normals(&mesh);
+            assert_outward_normals(&mesh);
+            assert_closed(&mesh);
+        }
+    }
+
+    #[test]
+    fn test_cove_edges() {
+        let rounded_box = RoundedBox::new(Vec3::new(1.0, 2.0, 3.0), -0.2);
+        let half_size = rounded_box.size / 2.0;
+        for options in MESH_OPTIONS {
+            for tessellation in [CornerTessellation::UvSphere, CornerTessellation::QuadSphere] {
+                println!("options={:?} tessellation={:?}", options, tessellation);
+                let mesh = rounded_box
+                    .mesh()
+                    .with_tessellation(tessellation)
+                    .with_options(options)
+                    .build();
+                assert_no_degenerates(&mesh);
+                assert_no_duplicates(&mesh);
+                assert_closed(&mesh);
+                assert_bounds(&mesh, rounded_box.size);
+                assert_unit_normals(&mesh);
+                assert_outward_normals(&mesh);
+
+                // The vertical coves are quarter-cylinders around the edges of the unrounded
+                // box, whose normals point towards their axes away from the creases
+                let mesh = rounded_box
+                    .mesh()
+                    .with_tessellation(tessellation)
+                    .with_segments(UVec3::new(1, 1, 4))
+                    .with_options(options)
+                    .build();
+                let pos = vertex_positions(&mesh);
+                let normals = vertex_normals(&mesh);
+                let mut cove_vertices = 0;
+                for (&p, &n) in pos.iter().zip(normals) {
+                    let (p, n) = (Vec3::from(p), Vec3::from(n));
+                    let axis = (half_size.xy() * p.xy().signum()).extend(p.z);
+                    let offset = axis - p;
+                    assert!(offset.length() > 0.2 - 1e-5, "p={}", p);
+                    let within = p.abs().cmplt(half_size - Vec3::new(1e-5, 1e-5, 0.2 + 1e-5));
+                    if (offset.length() - 0.2).abs() < 1e-5 && within.all() {
+                        cove_vertices += 1;
+                        assert!(n.abs_diff_eq(offset / 0.2, 1e-4), "p={} n={}", p, n);
+                    }
+                }
+                assert!(cove_vertices > 0);
+
+                let mesh = rounded_box
+                    .mesh()
+                    .with_tessellation(tessellation)
+                    .with_shell(0.1)
+                    .with_options(options)
+                    .build();
+                assert_no_degenerates(&mesh);
+                assert_closed(&mesh);
+                assert_bounds(&mesh, rounded_box.size);
+                assert_unit_normals(&mesh);
+                assert_outward_normals(&mesh);
+            }
+
+            // Coved top edges meeting rounded sides
+            let mesh = rounded_box
+                .mesh()
+                .with_edge_radii(EdgeRadii::splat(0.2).with_face(0, -0.2))
+                .with_options(options)
+                .build();
+            assert_no_degenerates(&mesh);
+            assert_closed(&mesh);
+            assert_bounds(&mesh, rounded_box.size);
+            assert_unit_normals(&mesh);
+            assert_outward_normals(&mesh);
+        }
+    }
+
+    #[test]
+    fn test_superellipse_profile() {
+        for options in MESH_OPTIONS {
+            println!("options={:?}", options);
+            let rounded_box = RoundedBox::new(Vec3::splat(2.0), 0.5);
+            let circular = rounded_box.mesh().with_options(options).build();
+            let mesh = rounded_box
+                .mesh()
+                .with_profile(CornerProfile::Superellipse(2.0))
+                .with_options(options)
+                .build();
+            for (id, values) in mesh.attributes() {
+                assert_eq!(
+                    values.get_bytes(),
+                    circular.attribute(id.id).unwrap().get_bytes()
+                );
+            }
+
+            for exponent in [1.5, 4.0, 10.0] {
+                let mesh = rounded_box
+                    .mesh()
+                    .with_profile(CornerProfile::Superellipse(exponent))
+                    .with_options(options)
+                    .build();
+                assert_no_degenerates(&mesh);
+                assert_no_duplicates(&mesh);
+                assert_bounds(&mesh, rounded_box.size);
+                assert_unit_normals(&mesh);
+                assert_outward_normals(&mesh);
+
+                // Corner vertices lie on a superellipsoid, with the normal of its surface
+                let pos = vertex_positions(&mesh);
+                let normals = vertex_normals(&mesh);
+                for (&p, &n) in pos.iter().zip(normals) {
+                    let offset = (Vec3::from(p).abs() - 0.5) / 0.5;
+                    if offset.cmple(Vec3::ZERO).any() {
+                        continue;
+                    }
+                    let distance = offset.powf(exponent).element_sum();
+                    assert!((distance - 1.0).abs() < 1e-4, "p={:?}", p);
+                    let gradient = offset.powf(exponent - 1.0).normalize();
+                    assert!(
+                        gradient.abs_diff_eq(Vec3::from(n).abs(), 1e-4),
+                        "p={:?} n={:?}",
+                        p,
+                        n
+                    );
+                }
+            }
+
+            let mesh = rounded_box
+                .mesh()
+                .with_edge_radii(EdgeRadii {
+                    x: [0.1, 0.2, 0.3, 0.4],
+                    y: [0.5, 0.1, 0.2, 0.3],
+                    z: [0.4, 0.5, 0.1, 0.2],
+                })
+                .with_profile(CornerProfile::Superellipse(4.0))
+                .with_options(options)
+                .build();
+            assert_bounds(&mesh, rounded_box.size);
+            assert_unit_normals(&mesh);
+            assert_outward_normals(&mesh);
+        }
+    }
+
+    #[test]
+    fn test_curve_profile() {
+        use bevy::math::curve::{FunctionCurve, Interval};
+
+        // A sampled quarter circle matches the circular profile
+        let circle = ProfileCurve::new(FunctionCurve::new(Interval::UNIT, |t| {
+            Vec2::from_angle(t * FRAC_PI_2)
+        }))
+        .unwrap();
+        for subdivisions in 1..=6 {
+            for options in MESH_OPTIONS {
+                println!("subdivisions={} options={:?}", subdivisions, options);
+                let builder = RoundedBox::new(Vec3::new(1.0, 2.0, 3.0), 0.3)
+                    .mesh()
+                    .with_subdivisions(subdivisions)
+                    .with_options(options);
+                let circular_mesh = builder.build();
+                let curve_mesh = builder
+                    .with_profile(CornerProfile::Curve(circle.clone()))
+                    .build();
+                for (attribute, tolerance) in [
+                    (Mesh::ATTRIBUTE_POSITION, 1e-4),
+                    (Mesh::ATTRIBUTE_NORMAL, 1e-3),
+                ] {
+                    let circular_values = circular_mesh.attribute(attribute).unwrap().as_float3();
+                    let curve_values = curve_mesh.attribute(attribute).unwrap().as_float3();
+                    for (&a, &b) in circular_values.unwrap().iter().zip(curve_values.unwrap()) {
+                        assert!(
+                            Vec3::from(a).abs_diff_eq(b.into(), tolerance),
+                            "a={:?} b={:?}",
+                            a,
+                            b
+                        );
+                    }
+                }
+            }
+        }
+
+        // A curve without bounds cannot be sampled
+        let unbounded = FunctionCurve::new(Interval::EVERYWHERE, Vec2::splat);
+        assert!(ProfileCurve::new(unbounded).is_none());
+
+        // An ogee, which is concave near one face and convex near the other
+        let ogee = ProfileCurve::new(FunctionCurve::new(Interval::UNIT, |t| {
+            Vec2::new(1.0 - t, t) + 0.15 * (TAU * t).sin() * Vec2::ONE.normalize()
+        }))
+        .unwrap();
+        for subdivisions in 1..=6 {
+            for options in MESH_OPTIONS {
+                println!("subdivisions={} options={:?}", subdivisions, options);
+                let mesh = RoundedBox::new(Vec3::ONE, 0.3)
+                    .mesh()
+                    .with_subdivisions(subdivisions)
+                    .with_options(options)
+                    .with_profile(CornerProfile::Curve(ogee.clone()))
+                    .build();
+                assert_no_degenerates(&mesh);
+                assert_bounds(&mesh, Vec3::ONE);
+                assert_unit_normals(&mesh);
+                assert_closed(&mesh);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chamfer_profile() {
+        for options in MESH_OPTIONS {
+            println!("options={:?}", options);
+            let rounded_box = RoundedBox::new(Vec3::new(1.0, 2.0, 3.0), 0.2);
+            let mesh = rounded_box
+                .mesh()
+                .with_profile(CornerProfile::Chamfer)
+                .with_options(options)
+                .build();
+            assert!(mesh.indices().is_none());
+            assert_bounds(&mesh, rounded_box.size);
+            assert_unit_normals(&mesh);
+            let pos = vertex_positions(&mesh);
+            let normals = vertex_normals(&mesh);
+            for (tri, tri_normals) in pos.chunks_exact(3).zip(normals.chunks_exact(3)) {
+                assert!(tri_normals.iter().all(|&n| n == tri_normals[0]));
+                let normal = Vec3::from(tri_normals[0]);
+                let centroid = tri.iter().copied().map(Vec3::from).sum::<Vec3>() / 3.0;
+                assert!(
+                    normal.dot(centroid) > 0.0,
+                    "normal={} tri={:?}",
+                    normal,
+                    tri
+                );
+
+                // Faces, bevels of edges and bevels of corners each have one, two or three
+                // equal components
+                let mut components = normal.abs().to_array();
+                components.sort_by(f32::total_cmp);
+                let equal = components
+                    .iter()
+                    .filter(|&&c| c > 1e-4)
+                    .all(|&c| (c - components[2]).abs() < 1e-4);
+                assert!(equal, "normal={} tri={:?}", normal, tri);
+            }
+
+            // The bevels are not subdivided
+            for tessellation in [CornerTessellation::UvSphere, CornerTessellation::QuadSphere] {
+                let subdivided = rounded_box
+                    .mesh()
+                    .with_profile(CornerProfile::Chamfer)
+                    .with_subdivisions(8)
+                    .with_tessellation(tessellation)
+                    .with_options(options)
+                    .build();
+                assert_eq!(subdivided.count_vertices(), mesh.count_vertices());
+            }
+        }
+    }
+
+    #[test]
+    fn test_stepped_profile() {
+        let rounded_box = RoundedBox::new(Vec3::new(1.0, 2.0, 3.0), 0.2);
+        for options in MESH_OPTIONS {
+            for steps in 1..=4 {
+                println!("options={:?} steps={}", options, steps);
+                let mesh = rounded_box
+                    .mesh()
+                    .with_profile(CornerProfile::Stepped(steps))
+                    .with_options(options)
+                    .build();
+                assert!(mesh.indices().is_none());
+                assert_bounds(&mesh, rounded_box.size);
+                assert_unit_normals(&mesh);
+                let pos = vertex_positions(&mesh);
+                let normals = vertex_normals(&mesh);
+                for (tri, tri_normals) in pos.chunks_exact(3).zip(normals.chunks_exact(3)) {
+                    // Every step is aligned with the axes
+                    let normal = Vec3::from(tri_normals[0]);
+                    assert!(
+                        normal.abs().max_element() > 1.0 - 1e-4,
+                        "normal={} tri={:?}",
+                        normal,
+                        tri
+                    );
+                    let centroid = tri.iter().copied().map(Vec3::from).sum::<Vec3>() / 3.0;
+                    assert!(
+                        normal.dot(centroid) > 0.0,
+                        "normal={} tri={:?}",
+                        normal,
+                        tri
+                    );
+                }
+
+                // The steps of each face lie on its side of the box
+                #[cfg(feature = "uvf")]
+                if let Some(VertexAttributeValues::Uint32(faces)) = mesh.attribute(ATTRIBUTE_FACE) {
+                    let face_normals = [Vec3::Z, Vec3::Y, -Vec3::X, -Vec3::Y, Vec3::X, -Vec3::Z];
+                    for (tri_faces, tri_normals) in
+                        faces.chunks_exact(3).zip(normals.chunks_exact(3))
+                    {
+                        assert!(tri_faces.iter().all(|&face| face == tri_faces[0]));
+                        let face_normal = face_normals[tri_faces[0] as usize];
+                        assert!(face_normal.dot(tri_normals[0].into()) > -1e-4);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_shell() {
+        for options in MESH_OPTIONS {
+            println!("options={:?}", options);
+            let rounded_box = RoundedBox::new(Vec3::new(1.0, 2.0, 3.0), 0.2);
+            let outer = rounded_box.mesh().with_options(options).build();
+            let mesh = rounded_box
+                .mesh()
+                .with_shell(0.1)
+                .with_options(options)
+                .build();
+            assert_eq!(mesh.count_vertices(), 2 * outer.count_vertices());
+            assert_no_degenerates(&mesh);
+            assert_no_duplicates(&mesh);
+            assert_bounds(&mesh, rounded_box.size);
+            assert_unit_normals(&mesh);
+            assert_outward_normals(&mesh);
+
+            let pos = vertex_positions(&mesh);
+            let normals = vertex_normals(&mesh);
+            let inner_size = rounded_box.size - 0.2;
+            for (i, (&p, &n)) in pos.iter().zip(normals).enumerate() {
+                let inner = i >= outer.count_vertices();
+                if inner {
+                    assert!(Vec3::from(p).abs().cmple(0.5 * inner_size + 1e-5).all());
+                }
+                assert_eq!(Vec3::from(p).dot(n.into()) < 0.0, inner, "p={:?}", p);
+            }
+            #[cfg(feature = "uvf")]
+            if let Some(VertexAttributeValues::Uint32(faces)) = mesh.attribute(ATTRIBUTE_FACE) {
+                let (outer_faces, inner_faces) = faces.split_at(outer.count_vertices());
+                assert!(outer_faces.iter().all(|&face| face < 6));
+                assert!(inner_faces.iter().all(|&face| (6..12).contains(&face)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverted() {
+        for options in MESH_OPTIONS {
+            println!("options={:?}", options);
+            let rounded_box = RoundedBox::new(Vec3::new(1.0, 2.0, 3.0), 0.2);
+            let mesh = rounded_box.mesh().with_options(options).build();
+            let inverted = rounded_box
+                .mesh()
+                .with_options(options.with_inverted())
+                .build();
+            assert_unit_normals(&inverted);
+            assert_outward_normals(&inverted);
+            assert_eq!(
+                mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+                    .unwrap()
+                    .get_bytes(),
+                inverted
+                    .attribute(Mesh::ATTRIBUTE_POSITION)
+                    .unwrap()
+                    .get_bytes()
+            );
+            let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+            let inverted_indices: Vec<usize> = inverted.indices().unwrap().iter().collect();
+            for (a, b) in indices
+                .chunks_exact(3)
+                .zip(inverted_indices.chunks_exact(3))
+            {
+                assert_eq!([a[0], a[2], a[1]], b);
+            }
+            let (
+                Some(VertexAttributeValues::Float32x3(normals)),
+                Some(VertexAttributeValues::Float32x3(inverted_normals)),
+            ) = (
+                mesh.attribute(Mesh::ATTRIBUTE_NORMAL),
+                inverted.attribute(Mesh::ATTRIBUTE_NORMAL),
+            )
+            else {
+                panic!("missing normals");
+            };
+            for (&a, &b) in normals.iter().zip(inverted_normals) {
+                assert_eq!(-Vec3::from(a), Vec3::from(b));
+            }
+            if let (
+                Some(VertexAttributeValues::Float32x2(uvs)),
+                Some(VertexAttributeValues::Float32x2(inverted_uvs)),
+            ) = (
+                mesh.attribute(Mesh::ATTRIBUTE_UV_0),
+                inverted.attribute(Mesh::ATTRIBUTE_UV_0),
+            ) {
+                for (&[u, v], &b) in uvs.iter().zip(inverted_uvs) {
+                    assert_eq!([1.0 - u, v], b);
+                }
+            }
+
+            // The inner surface of an inverted shell faces outwards
+            let mesh = rounded_box
+                .mesh()
+                .with_shell(0.1)
+                .with_options(options.with_inverted())
+                .build();
+            assert_outward_normals(&mesh);
+        }
+    }
+
+    #[test]
+    fn test_pole_axis() {
+        let rounded_box = RoundedBox {
+            size: Vec3::new(1.0, 2.0, 3.0),
+            radius: Vec3::new(0.1, 0.2, 0.3),
+        };
+        let edge_radii = EdgeRadii {
+            x: [0.1, 0.2, 0.3, 0.4],
+            y: [0.5, 0.1, 0.2, 0.3],
+            z: [0.4, 0.5, 0.1, 0.2],
+        };
+        let segments = UVec3::new(1, 2, 3);
+        for (pole_axis, pole) in [(PoleAxis::X, Vec3::X), (PoleAxis::Y, Vec3::Y)] {
+            for options in MESH_OPTIONS {
+                println!("pole_axis={:?} options={:?}", pole_axis, options);
+                let onto_z = |v: Vec3| Vec3::from_array(pole_axis.onto_z(v.to_array()));
+                let [x, y, z] = pole_axis.onto_z([edge_radii.x, edge_radii.y, edge_radii.z]);
+                for with_edge_radii in [false, true] {
+                    let mut builder = rounded_box
+                        .mesh()
+                        .with_pole_axis(pole_axis)
+                        .with_segments(segments)
+                        .with_options(options);
+                    let mut z_builder = RoundedBox {
+                        size: onto_z(rounded_box.size),
+                        radius: onto_z(rounded_box.radius),
+                    }
+                    .mesh()
+                    .with_segments(onto_z(segments.as_vec3()).as_uvec3())
+                    .with_options(options);
+                    if with_edge_radii {
+                        builder = builder.with_edge_radii(edge_radii);
+                        z_builder = z_builder.with_edge_radii(EdgeRadii { x, y, z });
+                    }
+                    let mesh = builder.build();
+                    let z_mesh = z_builder.build();
+                    assert_bounds(&mesh, rounded_box.size);
+                    assert_unit_normals(&mesh);
+                    assert_outward_normals(&mesh);
+
+                    // The same mesh as with poles on Z, with its axes permuted
+                    for (id, values) in z_mesh.attributes() {
+                        let permuted = mesh.attribute(id.id).unwrap();
+                        match values.as_float3() {
+                            Some(values) => {
+                                let values: Vec<[f32; 3]> =
+                                    values.iter().map(|&v| pole_axis.onto_axis(v)).collect();
+                                assert_eq!(permuted.as_float3().unwrap(), values);
+                            }
+                            None => assert_eq!(permuted.get_bytes(), values.get_bytes()),
+                        }
+                    }
+                    assert!(mesh
+                        .indices()
+                        .unwrap()
+                        .iter()
+                        .eq(z_mesh.indices().unwrap().iter()));
+
+                    // Face 0 is at the positive end of the pole axis
+                    if let Some(VertexAttributeValues::Uint32(faces)) =
+                        mesh.attribute(ATTRIBUTE_FACE)
+                    {
+                        let normals = vertex_normals(&mesh);
+                        for (&face, &n) in faces.iter().zip(normals) {
+                            let alignment = Vec3::from(n).dot(pole);
+                            match face {
+                                0 => assert!(alignment > 0.0),
+                                5 => assert!(alignment < 0.0),
+                                _ => assert!(alignment.abs() < 0.99),
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_clockwise() {
+        fn assert_reversed(mesh: &Mesh, clockwise: &Mesh) {
+            for (id, values) in mesh.attributes() {
+                assert_eq!(
+                    values.get_bytes(),
+                    clockwise.attribute(id.id).unwrap().get_bytes()
+                );
+            }
+            let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+            let clockwise_indices: Vec<usize> = clockwise.indices().unwrap().iter().collect();
+            assert_eq!(indices.len(), clockwise_indices.len());
+            for (a, b) in indices
+                .chunks_exact(3)
+                .zip(clockwise_indices.chunks_exact(3))
+            {
+                assert_eq!([a[0], a[2], a[1]], b);
+            }
+        }
+
+        for options in MESH_OPTIONS {
+            println!("options={:?}", options);
+            let clockwise = options.with_clockwise();
+            for options in [options, options.with_inverted()] {
+                let builder = RoundedBox::default().mesh().with_shell(0.05);
+                assert_reversed(
+                    &builder.clone().with_options(options).build(),
+                    &builder
+                        .clone()
+                        .with_options(options.with_clockwise())
+                        .build(),
+                );
+                let builder = builder.with_tessellation(CornerTessellation::QuadSphere);
+                assert_reversed(
+                    &builder.clone().with_options(options).build(),
+                    &builder.with_options(options.with_clockwise()).build(),
+                );
+            }
+            let builder = RoundedCylinder::default().mesh();
+            assert_reversed(
+                &builder.with_options(options).build(),
+                &builder.with_options(clockwise).build(),
+            );
+            let builder = RoundedPrism::default().mesh();
+            assert_reversed(
+                &builder.with_options(options).build(),
+                &builder.with_options(clockwise).build(),
+            );
+            let builder = RoundedFrustum::default().mesh();
+            assert_reversed(
+                &builder.with_options(options).build(),
+                &builder.with_options(clockwise).build(),
+            );
+            let builder = Keycap::default().mesh();
+            assert_reversed(
+                &builder.with_options(options).build(),
+                &builder.with_options(clockwise).build(),
+            );
+
+            // Flat normals still point outwards
+            let mesh = RoundedBox::default()
+                .mesh()
+                .with_profile(CornerProfile::Chamfer)
+                .with_options(clockwise)
+                .build();
+            let pos = vertex_positions(&mesh);
+            let normals = vertex_normals(&mesh);
+            for (&p, &n) in pos.iter().zip(normals) {
+                assert!(Vec3::from(p).dot(n.into()) > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_omitted_faces() {
+        const FACE_PLANES: [(usize, f32); 6] = [
+            (2, 1.0),
+            (1, 1.0),
+            (0, -1.0),
+            (1, -1.0),
+            (0, 1.0),
+            (2, -1.0),
+        ];
+        let rounded_box = RoundedBox::new(Vec3::new(1.0, 2.0, 3.0), 0.2);
+        let triangles_in_planes = |mesh: &Mesh| {
+            let pos = vertex_positions(mesh);
+            let indices = mesh.indices().unwrap();
+            let mut counts = [0; 6];
+            let mut it = indices.iter();
+            while let (Some(a), Some(b), Some(c)) = (it.next(), it.next(), it.next()) {
+                for (face, &(axis, sign)) in FACE_PLANES.iter().enumerate() {
+                    let plane = sign * 0.5 * rounded_box.size[axis];
+                    if [a, b, c].iter().all(|&i| pos[i][axis] == plane) {
+                        counts[face] += 1;
+                    }
+                }
+            }
+            counts
+        };
+        for options in MESH_OPTIONS {
+            println!("options={:?}", options);
+            let mesh = rounded_box.mesh().with_options(options).build();
+            let counts = triangles_in_planes(&mesh);
+            assert!(counts.iter().all(|&count| count > 0));
+            for face in 0..6 {
+                let open = rounded_box
+                    .mesh()
+                    .with_options(options)
+                    .without_face(face)
+                    .build();
+                assert_no_degenerates(&open);
+                assert_outward_normals(&open);
+                assert_eq!(
+                    open.indices().unwrap().len(),
+                    mesh.indices().unwrap().len() - 3 * counts[face as usize]
+                );
+                let mut expected = counts;
+                expected[face as usize] = 0;
+                assert_eq!(triangles_in_planes(&open), expected);
+            }
+            let tray = rounded_box.mesh().with_options(options).without_face(0);
+            let drawer = tray.without_face(1).build();
+            assert_eq!(triangles_in_planes(&drawer)[..2], [0, 0]);
+        }
+    }
+
+    #[test]
+    fn test_wireframe() {
+        let rounded_box = RoundedBox::new(Vec3::new(1.0, 2.0, 3.0), 0.2);
+        let segments = UVec3::new(2, 3, 4);
+        let key = |p: [f32; 3]| p.map(|c| (c + 0.0).to_bits());
+        for tessellation in [CornerTessellation::UvSphere, CornerTessellation::QuadSphere] {
+            for options in MESH_OPTIONS {
+                for pole_axis in [PoleAxis::Z, PoleAxis::Y] {
+                    println!(
+                        "tessellation={:?} options={:?} pole_axis={:?}",
+                        tessellation, options, pole_axis
+                    );
+                    let builder = rounded_box
+                        .mesh()
+                        .with_subdivisions(3)
+                        .with_segments(segments)
+                        .with_tessellation(tessellation)
+                        .with_pole_axis(pole_axis)
+                        .with_options(options);
+                    let solid = builder.build();
+                    let vertices: HashSet<_> =
+                        vertex_positions(&solid).iter().map(|&p| key(p)).collect();
+                    let lines = |builder: RoundedBoxWireframeMeshBuilder| {
+                        let mesh = builder.build();
+                        assert_eq!(mesh.primitive_topology(), PrimitiveTopology::LineList);
+                        let pos = vertex_positions(&mesh);
+                        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+                        assert_eq!(indices.len() % 2, 0);
+                        let mut set = HashSet::new();
+                        for line in indices.chunks_exact(2) {
+                            let [a, b] = [line[0], line[1]].map(|i| key(pos[i]));
+                            assert_ne!(a, b);
+                            assert!(vertices.contains(&a) && vertices.contains(&b));
+                            assert!(set.insert((a.min(b), a.max(b))), "p={:?}", pos[line[0]]);
+                        }
+                        set
+                    };
+
+                    // The outlines of the flat regions lie in the planes of the faces
+                    let outlines = lines(builder.clone().wireframe());
+                    assert_eq!(
+                        outlines.len(),
+                        8 * (segments.x + segments.y + segments.z) as usize
+                    );
+                    for &(a, b) in &outlines {
+                        let [a, b] = [a, b].map(|p| Vec3::from(p.map(f32::from_bits)));
+                        let on_face = |p: Vec3| p.abs().cmpeq(rounded_box.size / 2.0);
+                        assert!((on_face(a) & on_face(b)).any(), "a={} b={}", a, b);
+                    }
+
+                    // Isolines cross the rounded regions, and an omitted face leaves its rim
+                    let isolines = lines(builder.clone().wireframe().with_isolines());
+                    assert!(isolines.is_superset(&outlines));
+                    assert!(isolines.len() > outlines.len());
+                    assert_eq!(lines(builder.without_face(0).wireframe()), outlines);
+                }
+            }
         }
+
+        // Sharp edges are drawn once
+        let sharp = RoundedBox::new(Vec3::ONE, 0.0).mesh().wireframe().build();
+        assert_eq!(sharp.indices().unwrap().len(), 2 * 12);
     }
 }
@@ -0,0 +1,290 @@
+use std::{collections::HashMap, f32::consts::FRAC_PI_4};
+
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+    },
+};
+
+#[cfg(feature = "uvf")]
+use crate::ATTRIBUTE_FACE;
+use crate::{flat_segments, quad_lines, BoxEdges, BoxPrimitives, RoundedBoxMeshOptions};
+
+/// The faces of a box, numbered as for [`ATTRIBUTE_FACE`](crate::ATTRIBUTE_FACE), as the axis
+/// and sign of their normal and the axes their grids run along. The axes are ordered so that the
+/// grids wind anticlockwise when seen from outside.
+const FACES: [(usize, f32, usize, usize); 6] = [
+    (2, 1.0, 0, 1),
+    (1, 1.0, 2, 0),
+    (0, -1.0, 2, 1),
+    (1, -1.0, 0, 2),
+    (0, 1.0, 1, 2),
+    (2, -1.0, 1, 0),
+];
+
+/// Where a point of the subdivided cube lies along one axis.
+#[derive(Copy, Clone)]
+enum AxisPoint {
+    /// Within the rounded edges on the side of `sign`, at `fraction` of the way from the flat
+    /// region to the diagonal of the edges.
+    Rounded { sign: f32, fraction: f32 },
+    /// Within the flat region, at the given fraction of the way across it.
+    Flat(f32),
+}
+
+impl AxisPoint {
+    /// Decodes a coordinate of the cube, which has `half` steps in each rounded edge and
+    /// `segments` steps across the flat region.
+    fn new(coord: u32, half: u32, segments: u32) -> Self {
+        if coord < half {
+            AxisPoint::Rounded {
+                sign: -1.0,
+                fraction: (half - coord) as f32 / half as f32,
+            }
+        } else if coord > half + segments {
+            AxisPoint::Rounded {
+                sign: 1.0,
+                fraction: (coord - half - segments) as f32 / half as f32,
+            }
+        } else {
+            AxisPoint::Flat((coord - half) as f32 / segments as f32)
+        }
+    }
+
+    /// Returns the component of the direction on the cube, spaced by equal angles.
+    fn direction(self) -> f32 {
+        match self {
+            AxisPoint::Rounded { sign, fraction } => sign * (fraction * FRAC_PI_4).tan(),
+            AxisPoint::Flat(_) => 0.0,
+        }
+    }
+
+    /// Returns the weights of the corners on the negative and positive sides.
+    fn weights(self) -> [f32; 2] {
+        match self {
+            AxisPoint::Rounded { sign, .. } if sign < 0.0 => [1.0, 0.0],
+            AxisPoint::Rounded { .. } => [0.0, 1.0],
+            AxisPoint::Flat(fraction) => [1.0 - fraction, fraction],
+        }
+    }
+}
+
+/// Builds a mesh with the faces of a [`RoundedBox`](crate::RoundedBox) as grids on a subdivided
+/// cube, whose vertices are placed as for `build_box_topology`.
+///
+/// The directions of the corners are spaced by equal angles along each axis of the cube, so that
+/// each corner is three quadrilateral patches of similar size and the faces have no poles.
+/// `subdivisions` is rounded up to an even number, as each face meets half of each edge.
+#[cfg_attr(not(feature = "uvf"), allow(unused_variables))]
+pub(crate) fn build_quad_sphere_topology<'a>(
+    subdivisions: usize,
+    segments: UVec3,
+    options: &RoundedBoxMeshOptions,
+    inner: bool,
+    primitives: BoxPrimitives,
+    mut vertex: impl FnMut(Vec3, Vec3) -> (Vec3, Vec3),
+    uv_edges: impl Fn(u32) -> &'a BoxEdges,
+) -> Mesh {
+    debug_assert!(subdivisions > 0);
+    let segments = flat_segments(segments, &mut vertex);
+    let half = (subdivisions as u32).div_ceil(2);
+    let size = 2 * half + segments;
+    let split_faces = options.is_split_faces();
+    let flip = inner != options.inverted;
+    let facing = if flip { -1.0 } else { 1.0 };
+    #[cfg(feature = "uvf")]
+    let first_face = if inner { 6 } else { 0 };
+
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut normals: Vec<[f32; 3]> = Vec::new();
+    #[cfg(feature = "uvf")]
+    let mut uvs: Vec<[f32; 2]> = Vec::new();
+    #[cfg(feature = "uvf")]
+    let mut faces: Vec<u32> = Vec::new();
+    let mut indices: Vec<u32> = Vec::new();
+    let mut quads: Vec<([u32; 4], Option<u32>)> = Vec::new();
+
+    // Vertices are shared between faces unless the faces are split
+    let mut shared: HashMap<UVec3, u32> = HashMap::new();
+    for (face, &(normal_axis, normal_sign, u_axis, v_axis)) in FACES.iter().enumerate() {
+        let face = face as u32;
+        let mut grid = Vec::with_capacity(((size[u_axis] + 1) * (size[v_axis] + 1)) as usize);
+        for j in 0..=size[v_axis] {
+            for i in 0..=size[u_axis] {
+                let mut coords = UVec3::ZERO;
+                coords[normal_axis] = if normal_sign > 0.0 {
+                    size[normal_axis]
+                } else {
+                    0
+                };
+                coords[u_axis] = i;
+                coords[v_axis] = j;
+                if let Some(&index) = shared.get(&coords) {
+                    grid.push(index);
+                    continue;
+                }
+
+                // Calculate coordinates and normal, interpolating across the flat regions
+                let points =
+                    [0, 1, 2].map(|axis| AxisPoint::new(coords[axis], half, segments[axis]));
+                let dir = Vec3::from_array(points.map(AxisPoint::direction)).normalize();
+                let weights = points.map(AxisPoint::weights);
+                let mut pos = Vec3::ZERO;
+                let mut normal = Vec3::ZERO;
+                for octant in 0..8 {
+                    let side = [0, 1, 2].map(|axis| (octant >> axis) & 1);
+                    let weight: f32 = (0..3).map(|axis| weights[axis][side[axis]]).product();
+                    if weight > 0.0 {
+                        let corner = Vec3::from_array(side.map(|side| 2.0 * side as f32 - 1.0));
+                        let (corner_pos, corner_normal) = vertex(corner, dir.abs() * corner);
+                        pos += weight * corner_pos;
+                        normal += weight * corner_normal;
+                    }
+                }
+                let index = positions.len() as u32;
+                positions.push(pos.to_array());
+                normals.push((facing * normal.normalize()).to_array());
+                if !split_faces {
+                    shared.insert(coords, index);
+                }
+                grid.push(index);
+
+                // Calculate texture coordinates from the lengths unrolled across the edges
+                #[cfg(feature = "uvf")]
+                if options.is_generate_uv() {
+                    let edges = uv_edges(face);
+                    let unrolled = |axis: usize| {
+                        let edge_axis = 3 - normal_axis - axis;
+                        let extent = |sign: f32| {
+                            let mut corner = Vec3::ZERO;
+                            corner[axis] = sign;
+                            corner[normal_axis] = normal_sign;
+                            (
+                                edges.core_extent(edge_axis, corner, axis),
+                                edges.rounded_length(edge_axis, corner, axis),
+                            )
+                        };
+                        let (min_core, min_rounded) = extent(-1.0);
+                        let (max_core, max_rounded) = extent(1.0);
+                        let core = min_core + max_core;
+                        let offset = match points[axis] {
+                            AxisPoint::Rounded { sign, fraction } if sign < 0.0 => {
+                                (1.0 - fraction) * min_rounded
+                            }
+                            AxisPoint::Rounded { fraction, .. } => {
+                                min_rounded + core + fraction * max_rounded
+                            }
+                            AxisPoint::Flat(fraction) => min_rounded + fraction * core,
+                        };
+                        offset / (min_rounded + core + max_rounded)
+                    };
+                    let uv = match face {
+                        0 => Vec2::new(unrolled(0), 1.0 - unrolled(1)),
+                        1 => Vec2::new(1.0 - unrolled(0), 1.0 - unrolled(2)),
+                        2 => Vec2::new(1.0 - unrolled(1), 1.0 - unrolled(2)),
+                        3 => Vec2::new(unrolled(0), 1.0 - unrolled(2)),
+                        4 => Vec2::new(unrolled(1), 1.0 - unrolled(2)),
+                        _ => Vec2::new(unrolled(0), unrolled(1)),
+                    };
+                    let uv = if options.inverted {
+                        Vec2::new(1.0 - uv.x, uv.y)
+                    } else {
+                        uv
+                    };
+                    uvs.push(uv.to_array());
+                }
+
+                // Calculate face index
+                #[cfg(feature = "uvf")]
+                if options.is_generate_face() {
+                    faces.push(first_face + face);
+                }
+            }
+        }
+
+        // Generate indices, excluding the flat region of an omitted face and the degenerate
+        // triangles of sharp edges
+        let stride = size[u_axis] + 1;
+        let is_flat = |coord: u32, axis: usize| coord >= half && coord < half + segments[axis];
+        for j in 0..size[v_axis] {
+            for i in 0..size[u_axis] {
+                let flat = is_flat(i, u_axis) && is_flat(j, v_axis);
+                if flat && options.is_face_omitted(face) {
+                    continue;
+                }
+                let index = |i: u32, j: u32| grid[(j * stride + i) as usize];
+                let jj = index(i, j);
+                let kj = index(i + 1, j);
+                let jk = index(i, j + 1);
+                let kk = index(i + 1, j + 1);
+                if primitives != BoxPrimitives::Triangles {
+                    quads.push(([jj, kj, kk, jk], flat.then_some(face)));
+                    continue;
+                }
+                // Split the quad along the diagonal whose triangles best follow the normals,
+                // as the corners of unequal edges can be far from planar
+                let triangles =
+                    [[[jj, kj, kk], [jj, kk, jk]], [[jj, kj, jk], [kj, kk, jk]]].map(|triangles| {
+                        triangles.map(|triangle| {
+                            let [a, b, c] =
+                                triangle.map(|index| Vec3::from(positions[index as usize]));
+                            let normal = triangle
+                                .map(|index| facing * Vec3::from(normals[index as usize]))
+                                .into_iter()
+                                .sum::<Vec3>();
+                            let alignment = (b - a).cross(c - a).normalize_or_zero().dot(normal);
+                            (triangle, a != b && b != c && c != a, alignment)
+                        })
+                    });
+                let score = |triangles: &[([u32; 3], bool, f32); 2]| {
+                    triangles
+                        .iter()
+                        .filter(|(_, valid, _)| *valid)
+                        .map(|(_, _, alignment)| *alignment)
+                        .fold(f32::MAX, f32::min)
+                };
+                let [first, second] = triangles;
+                let best = if score(&second) > score(&first) {
+                    second
+                } else {
+                    first
+                };
+                for (triangle, valid, _) in best {
+                    if valid {
+                        indices.extend(triangle);
+                    }
+                }
+            }
+        }
+    }
+    if options.is_reversed(flip) {
+        for triangle in indices.chunks_exact_mut(3) {
+            triangle.swap(1, 2);
+        }
+    }
+
+    let mut mesh = match primitives {
+        BoxPrimitives::Triangles => Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        ),
+        BoxPrimitives::Lines { isolines } => {
+            indices = quad_lines(&quads, &positions, isolines);
+            Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::default())
+        }
+    };
+    mesh.insert_indices(Indices::U32(indices));
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    #[cfg(feature = "uvf")]
+    if options.is_generate_uv() {
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    }
+    #[cfg(feature = "uvf")]
+    if options.is_generate_face() {
+        mesh.insert_attribute(ATTRIBUTE_FACE, faces);
+    }
+    mesh
+}
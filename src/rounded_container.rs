@@ -0,0 +1,314 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+
+use crate::{build_box_topology, BoxEdges, BoxPrimitives, CornerProfile, RoundedBoxMeshOptions};
+
+/// An open-topped box with rounded edges and walls of a given thickness, such as a tray, a cup
+/// or a planter.
+///
+/// The container is open towards +Z. The top of its walls is a rounded rim whose radius is half
+/// the thickness, joining the outer walls to the walls of the cavity.
+///
+/// The faces are numbered as for [`RoundedBox`](crate::RoundedBox), except that the top face 0
+/// is the inside of the container, from the crest of the rim down to the floor of the cavity.
+/// The texture coordinates are laid out as for a box of the same size.
+#[derive(Copy, Clone, Debug)]
+pub struct RoundedContainer {
+    /// The outer dimensions of the container.
+    pub size: Vec3,
+    /// The radius of the outer corners and edges.
+    ///
+    /// The corners and edges of the cavity have this radius less the thickness, or are sharp
+    /// if the thickness is larger.
+    pub radius: f32,
+    /// The thickness of the walls and the base, which must be less than half the width and
+    /// depth of the container, and leave room for the rim and the edges above the base.
+    pub thickness: f32,
+}
+
+impl RoundedContainer {
+    /// Creates a rounded container.
+    pub const fn new(size: Vec3, radius: f32, thickness: f32) -> Self {
+        Self {
+            size,
+            radius,
+            thickness,
+        }
+    }
+}
+
+impl Default for RoundedContainer {
+    fn default() -> Self {
+        Self::new(Vec3::ONE, 0.2, 0.1)
+    }
+}
+
+impl Meshable for RoundedContainer {
+    type Output = RoundedContainerMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        RoundedContainerMeshBuilder {
+            container: *self,
+            subdivisions: 4,
+            options: RoundedBoxMeshOptions::DEFAULT,
+        }
+    }
+}
+
+impl From<RoundedContainer> for Mesh {
+    fn from(value: RoundedContainer) -> Self {
+        value.mesh().build()
+    }
+}
+
+/// A builder used for creating a [`Mesh`] with a [`RoundedContainer`] shape.
+#[derive(Copy, Clone, Debug)]
+pub struct RoundedContainerMeshBuilder {
+    /// The [`RoundedContainer`] shape.
+    pub container: RoundedContainer,
+    /// The number of sectors in each corner, and of stacks in each quarter of the edges of the
+    /// cavity and in the inner quarter of the rim.
+    ///
+    /// The outside and the inside share the stacks of the corners of a box, so the outer
+    /// quarter of the rim has `2 * subdivisions + 1` stacks and the edges of the base
+    /// `4 * subdivisions + 2`.
+    pub subdivisions: usize,
+    /// Mesh generation options.
+    pub options: RoundedBoxMeshOptions,
+}
+
+impl Default for RoundedContainerMeshBuilder {
+    fn default() -> Self {
+        RoundedContainer::default().mesh()
+    }
+}
+
+/// The edges of a [`RoundedContainer`] and of its cavity.
+struct ContainerEdges {
+    /// The edges of the outside of the container.
+    outer: BoxEdges,
+    /// The edges of the cavity, whose upper half is left open by the rim.
+    cavity: BoxEdges,
+    /// The radius of the rim.
+    rim_radius: f32,
+    /// The number of stacks in each quarter of the edges of the cavity.
+    subdivisions: u32,
+}
+
+impl ContainerEdges {
+    /// Returns the position and outward normal of the point which corresponds to `dir` on the
+    /// unit sphere, in the octant of `corner`.
+    ///
+    /// The lower corners are the corners of the base. The stacks of the upper corners run over
+    /// the rim, down the walls of the cavity and around the corners of its floor, which are
+    /// reached at the poles.
+    fn point(&self, corner: Vec3, dir: Vec3) -> (Vec3, Vec3) {
+        if corner.z < 0.0 {
+            let (inset, normal) = self
+                .outer
+                .corner_point(corner, dir, &CornerProfile::Circular);
+            return ((self.outer.half_size - inset) * corner, normal * corner);
+        }
+
+        // The lattice only has whole stacks, which run over the outer quarter of the rim, the
+        // inner quarter, the walls of the cavity and the corners of its floor
+        let n = self.subdivisions;
+        let unit = dir.abs();
+        let phi = unit.z.atan2(unit.xy().length()) / FRAC_PI_2;
+        let stack = (phi * (4 * n + 2) as f32).round() as u32;
+        let horizontal = unit.xy().normalize_or_zero() * corner.xy();
+        if stack <= 2 * n + 1 {
+            let angle = FRAC_PI_2 * stack as f32 / (2 * n + 1) as f32;
+            self.rim_point(corner, horizontal, angle)
+        } else if stack <= 3 * n + 1 {
+            let angle = FRAC_PI_2 * (stack - n - 1) as f32 / n as f32;
+            self.rim_point(corner, horizontal, angle)
+        } else {
+            let angle = FRAC_PI_2 * (stack - 3 * n - 2) as f32 / n as f32;
+            self.floor_point(corner, horizontal, angle)
+        }
+    }
+
+    /// Returns the position and outward normal of the point at `angle` around the rim, from the
+    /// outer walls to the walls of the cavity, in the `horizontal` direction from its corner.
+    fn rim_point(&self, corner: Vec3, horizontal: Vec2, angle: f32) -> (Vec3, Vec3) {
+        // The outline is the equator of a box whose edges parallel to Z are the corners
+        let offset = self.rim_radius * (1.0 - angle.cos());
+        let outline = BoxEdges {
+            half_size: (self.outer.half_size.xy() - offset).extend(0.0),
+            radii: [
+                [Vec3::ZERO; 4],
+                [Vec3::ZERO; 4],
+                self.outer.radii[2].map(|radius| (radius - offset).max(Vec3::ZERO)),
+            ],
+        };
+        let (inset, _) =
+            outline.corner_point(corner, horizontal.extend(0.0), &CornerProfile::Circular);
+        let z = self.outer.half_size.z - self.rim_radius * (1.0 - angle.sin());
+        let pos = ((outline.half_size - inset) * corner).with_z(z);
+        (pos, (angle.cos() * horizontal).extend(angle.sin()))
+    }
+
+    /// Returns the position and outward normal of the point at `angle` around the corners of
+    /// the floor of the cavity, from its walls to its floor, in the `horizontal` direction
+    /// from its corner.
+    fn floor_point(&self, corner: Vec3, horizontal: Vec2, angle: f32) -> (Vec3, Vec3) {
+        let corner = corner.with_z(-1.0);
+        let dir = (angle.cos() * horizontal).extend(-angle.sin());
+        let (inset, normal) = self
+            .cavity
+            .corner_point(corner, dir, &CornerProfile::Circular);
+        ((self.cavity.half_size - inset) * corner, -normal * corner)
+    }
+}
+
+impl MeshBuilder for RoundedContainerMeshBuilder {
+    fn build(&self) -> Mesh {
+        let RoundedContainer {
+            size,
+            radius,
+            thickness,
+        } = self.container;
+        debug_assert!(self.subdivisions > 0);
+        let half_size = size / 2.0;
+        let rim_radius = thickness / 2.0;
+        let inner_radius = (radius - thickness).max(0.0);
+        debug_assert!(
+            thickness > 0.0 && thickness < half_size.xy().min_element(),
+            "invalid thickness"
+        );
+        debug_assert!(
+            radius + rim_radius <= size.z && thickness + inner_radius + rim_radius <= size.z,
+            "container too shallow for its thickness and radius"
+        );
+
+        // The cavity is inset from the outside as for the inner surface of a shell
+        let outer = BoxEdges {
+            half_size,
+            radii: [[Vec3::splat(radius); 4]; 3],
+        };
+        let edges = ContainerEdges {
+            cavity: outer.inset(thickness),
+            outer,
+            rim_radius,
+            subdivisions: self.subdivisions as u32,
+        };
+
+        build_box_topology(
+            self.subdivisions,
+            4 * self.subdivisions + 2,
+            UVec3::ONE,
+            &self.options,
+            false,
+            BoxPrimitives::Triangles,
+            |corner, dir| edges.point(corner, dir),
+            |_| &edges.outer,
+        )
+    }
+}
+
+impl RoundedContainerMeshBuilder {
+    /// Sets the number of subdivisions.
+    pub const fn with_subdivisions(self, subdivisions: usize) -> Self {
+        RoundedContainerMeshBuilder {
+            subdivisions,
+            ..self
+        }
+    }
+
+    /// Sets the mesh generation options.
+    pub const fn with_options(self, options: RoundedBoxMeshOptions) -> Self {
+        RoundedContainerMeshBuilder { options, ..self }
+    }
+
+    /// Turn the mesh inside out, for viewing from inside.
+    pub const fn with_inverted(mut self) -> Self {
+        self.options = self.options.with_inverted();
+        self
+    }
+
+    /// Omit the flat quad of a face, numbered as for [`ATTRIBUTE_FACE`](crate::ATTRIBUTE_FACE).
+    pub const fn without_face(mut self, face: u32) -> Self {
+        self.options = self.options.without_face(face);
+        self
+    }
+
+    /// Enable generating [`Mesh::ATTRIBUTE_UV_0`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub const fn with_uv(mut self) -> Self {
+        self.options = self.options.with_uv();
+        self
+    }
+
+    /// Enable generating [`ATTRIBUTE_FACE`](crate::ATTRIBUTE_FACE). Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub const fn with_face(mut self) -> Self {
+        self.options = self.options.with_face();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    #[cfg(feature = "uvf")]
+    use crate::ATTRIBUTE_FACE;
+    #[cfg(feature = "uvf")]
+    use bevy::render::mesh::VertexAttributeValues;
+
+    #[test]
+    fn test_rounded_container() {
+        for subdivisions in 1..=6 {
+            println!("subdivisions={}", subdivisions);
+            // Rounded and sharp cavities
+            for container in [
+                RoundedContainer::new(Vec3::new(1.0, 2.0, 3.0), 0.3, 0.1),
+                RoundedContainer::new(Vec3::new(1.0, 2.0, 3.0), 0.1, 0.2),
+            ] {
+                for options in MESH_OPTIONS {
+                    let builder = container
+                        .mesh()
+                        .with_subdivisions(subdivisions)
+                        .with_options(options);
+                    let mesh = builder.build();
+                    assert_no_degenerates(&mesh);
+                    assert_no_duplicates(&mesh);
+                    assert_bounds(&mesh, container.size);
+                    assert_unit_normals(&mesh);
+                    assert_outward_normals(&mesh);
+                    assert_closed(&mesh);
+                    assert_outward_normals(&builder.with_inverted().build());
+                    #[cfg(feature = "uvf")]
+                    if let Some(VertexAttributeValues::Uint32(faces)) =
+                        mesh.attribute(ATTRIBUTE_FACE)
+                    {
+                        // The inside of the container is the top face, away from the corners
+                        let pos = vertex_positions(&mesh);
+                        let half_size = container.size / 2.0;
+                        let cavity = half_size.xy() - container.thickness - 1e-5;
+                        for (&face, &p) in faces.iter().zip(pos) {
+                            assert!(face < 6);
+                            let p = Vec3::from(p);
+                            if p.z < -half_size.z + 1e-5 {
+                                assert_eq!(face, 5, "p={}", p);
+                            } else if p.xy().abs().cmplt(cavity).all()
+                                && (p.xy().abs() - half_size.xy() + container.radius).min_element()
+                                    < 0.0
+                            {
+                                assert_eq!(face, 0, "p={}", p);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Invalid thicknesses are rejected
+        for thickness in [0.0, 0.6] {
+            let container = RoundedContainer::new(Vec3::new(1.0, 2.0, 3.0), 0.3, thickness);
+            assert!(std::panic::catch_unwind(|| container.mesh().build()).is_err());
+        }
+    }
+}
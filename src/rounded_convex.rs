@@ -0,0 +1,686 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    f32::consts::TAU,
+};
+
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+    },
+};
+
+use crate::RoundedBoxMeshOptions;
+#[cfg(feature = "uvf")]
+use crate::ATTRIBUTE_FACE;
+
+/// A convex polyhedron with rounded edges and corners, such as a tetrahedron, a wedge or the
+/// convex hull of a set of points.
+///
+/// As for a [`RoundedBox`](crate::RoundedBox), the flat faces lie in the faces of the
+/// polyhedron. The shape is the polyhedron inset by the radius and then expanded by it, so that
+/// the edges are cylinders and the corners patches of spheres. Faces which the inset shrinks to
+/// nothing are left out.
+///
+/// For [`ATTRIBUTE_FACE`](crate::ATTRIBUTE_FACE), the faces are numbered in the order of
+/// [`Self::planes`], and the rounded edges and corners are split between the faces whose
+/// normals are nearest.
+#[derive(Clone, Debug)]
+pub struct RoundedConvex {
+    /// The planes of the faces, as their outward unit normals and their distances from the
+    /// origin along the normals.
+    pub planes: Vec<(Vec3, f32)>,
+    /// The radius of the corners and edges, which may shrink the inset polyhedron to a polygon,
+    /// as for a slab twice the radius thick, but no further.
+    pub radius: f32,
+}
+
+impl RoundedConvex {
+    /// Creates a rounded polyhedron bounded by planes, given as outward normals and distances
+    /// from the origin. The normals need not be of unit length, and the distances are scaled
+    /// with them.
+    pub fn from_planes(planes: impl IntoIterator<Item = (Vec3, f32)>, radius: f32) -> Self {
+        let planes = planes
+            .into_iter()
+            .map(|(normal, distance)| {
+                let length = normal.length();
+                (normal / length, distance / length)
+            })
+            .collect();
+        Self { planes, radius }
+    }
+
+    /// Creates the rounded convex hull of points, which may include points inside it. The faces
+    /// are numbered in an arbitrary order.
+    ///
+    /// The hull is found by testing every triple of points, so it is meant for polyhedra with
+    /// tens of points rather than thousands.
+    pub fn from_vertices(vertices: impl IntoIterator<Item = Vec3>, radius: f32) -> Self {
+        let points: Vec<Vec3> = vertices.into_iter().collect();
+        let epsilon = tolerance(points.iter().map(|p| p.length()));
+        // Each face is found from the triple of its points which spans it best
+        let mut faces: Vec<((Vec3, f32), Vec<usize>, f32)> = Vec::new();
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                for k in j + 1..points.len() {
+                    let cross = (points[j] - points[i]).cross(points[k] - points[i]);
+                    let Some(normal) = cross.try_normalize() else {
+                        continue;
+                    };
+                    let distance = normal.dot(points[i]);
+                    let (mut above, mut below) = (false, false);
+                    for &p in &points {
+                        let height = normal.dot(p) - distance;
+                        above |= height > epsilon;
+                        below |= height < -epsilon;
+                    }
+                    let plane = match (above, below) {
+                        (false, _) => (normal, distance),
+                        (true, false) => (-normal, -distance),
+                        (true, true) => continue,
+                    };
+                    let on_plane: Vec<usize> = (0..points.len())
+                        .filter(|&i| (plane.0.dot(points[i]) - plane.1).abs() <= epsilon)
+                        .collect();
+                    let span = cross.length();
+                    match faces.iter_mut().find(|(_, other, _)| *other == on_plane) {
+                        Some(face) if face.2 < span => *face = (plane, on_plane, span),
+                        Some(_) => {}
+                        None => faces.push((plane, on_plane, span)),
+                    }
+                }
+            }
+        }
+        Self {
+            planes: faces.into_iter().map(|(plane, _, _)| plane).collect(),
+            radius,
+        }
+    }
+
+    /// Returns the corners of the unrounded polyhedron.
+    pub fn vertices(&self) -> Vec<Vec3> {
+        let epsilon = tolerance(self.planes.iter().map(|(_, distance)| distance.abs()));
+        plane_corners(&self.planes, epsilon)
+    }
+}
+
+impl Default for RoundedConvex {
+    fn default() -> Self {
+        let tetrahedron = [
+            Vec3::new(1.0, 1.0, 1.0),
+            Vec3::new(1.0, -1.0, -1.0),
+            Vec3::new(-1.0, 1.0, -1.0),
+            Vec3::new(-1.0, -1.0, 1.0),
+        ];
+        Self::from_vertices(tetrahedron.map(|v| 0.5 * v), 0.1)
+    }
+}
+
+impl Meshable for RoundedConvex {
+    type Output = RoundedConvexMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        RoundedConvexMeshBuilder {
+            convex: self.clone(),
+            subdivisions: 4,
+            options: RoundedBoxMeshOptions::DEFAULT,
+        }
+    }
+}
+
+impl From<RoundedConvex> for Mesh {
+    fn from(value: RoundedConvex) -> Self {
+        value.mesh().build()
+    }
+}
+
+/// A builder used for creating a [`Mesh`] with a [`RoundedConvex`] shape.
+#[derive(Clone, Debug)]
+pub struct RoundedConvexMeshBuilder {
+    /// The [`RoundedConvex`] shape.
+    pub convex: RoundedConvex,
+    /// The number of segments across each rounded edge, and of rows in each corner.
+    pub subdivisions: usize,
+    /// Mesh generation options.
+    ///
+    /// The texture coordinates of each face, and of the rounded regions split with it, are
+    /// projected onto its plane and span the extent of the whole shape.
+    pub options: RoundedBoxMeshOptions,
+}
+
+impl Default for RoundedConvexMeshBuilder {
+    fn default() -> Self {
+        RoundedConvex::default().mesh()
+    }
+}
+
+/// Returns the distance within which points are treated as the same, for a shape whose points
+/// lie within the largest of `distances` of the origin.
+fn tolerance(distances: impl Iterator<Item = f32>) -> f32 {
+    1e-4 * distances.fold(1e-3, f32::max)
+}
+
+/// Returns the corners of the polyhedron bounded by planes, each once.
+fn plane_corners(planes: &[(Vec3, f32)], epsilon: f32) -> Vec<Vec3> {
+    let mut corners: Vec<Vec3> = Vec::new();
+    for (i, &(ni, di)) in planes.iter().enumerate() {
+        for (j, &(nj, dj)) in planes.iter().enumerate().skip(i + 1) {
+            for &(nk, dk) in planes.iter().skip(j + 1) {
+                let matrix = Mat3::from_cols(ni, nj, nk).transpose();
+                if matrix.determinant().abs() < 1e-6 {
+                    continue;
+                }
+                let corner = matrix.inverse() * Vec3::new(di, dj, dk);
+                let inside = planes
+                    .iter()
+                    .all(|&(normal, distance)| normal.dot(corner) - distance <= epsilon);
+                if inside && !corners.iter().any(|&c| c.distance(corner) <= epsilon) {
+                    corners.push(corner);
+                }
+            }
+        }
+    }
+    corners
+}
+
+/// Interpolates between unit vectors by angle, returning the ends exactly.
+fn slerp(from: Vec3, to: Vec3, t: f32) -> Vec3 {
+    let angle = from.angle_between(to);
+    if t == 0.0 || angle < 1e-6 {
+        from
+    } else if t == 1.0 {
+        to
+    } else {
+        (from * ((1.0 - t) * angle).sin() + to * (t * angle).sin()) / angle.sin()
+    }
+}
+
+/// The vertices and triangles of a [`RoundedConvex`], before they are split between faces.
+struct Surface<'a> {
+    /// The outward normals of the faces.
+    face_normals: &'a [Vec3],
+    /// The positions and normals of the vertices, and the faces whose normals are nearest.
+    vertices: Vec<(Vec3, Vec3, u32)>,
+    /// The triangles facing outwards, and the faces whose normals are nearest.
+    triangles: Vec<([u32; 3], u32)>,
+}
+
+impl Surface<'_> {
+    /// Returns the face among `faces` whose normal is nearest to `normal`.
+    fn nearest_face(&self, normal: Vec3, faces: &[u32]) -> u32 {
+        faces
+            .iter()
+            .copied()
+            .max_by(|&a, &b| {
+                let alignment = |face: u32| normal.dot(self.face_normals[face as usize]);
+                alignment(a).total_cmp(&alignment(b))
+            })
+            .unwrap()
+    }
+
+    fn vertex(&mut self, pos: Vec3, normal: Vec3, faces: &[u32]) -> u32 {
+        let face = self.nearest_face(normal, faces);
+        self.vertices.push((pos, normal, face));
+        self.vertices.len() as u32 - 1
+    }
+
+    /// Adds a triangle unless it is degenerate, winding it to face outwards.
+    fn triangle(&mut self, triangle: [u32; 3], faces: &[u32]) {
+        let [a, b, c] = triangle.map(|i| self.vertices[i as usize].0);
+        if a == b || b == c || c == a {
+            return;
+        }
+        let normal: Vec3 = triangle.map(|i| self.vertices[i as usize].1).iter().sum();
+        let triangle = if (b - a).cross(c - a).dot(normal) < 0.0 {
+            [triangle[0], triangle[2], triangle[1]]
+        } else {
+            triangle
+        };
+        let face = self.nearest_face(normal, faces);
+        self.triangles.push((triangle, face));
+    }
+}
+
+impl MeshBuilder for RoundedConvexMeshBuilder {
+    fn build(&self) -> Mesh {
+        debug_assert!(self.subdivisions > 0);
+        let subdivisions = self.subdivisions as u32;
+        let RoundedConvex { planes, radius } = &self.convex;
+        let radius = *radius;
+        debug_assert!(radius >= 0.0);
+        let face_normals: Vec<Vec3> = planes.iter().map(|&(normal, _)| normal).collect();
+
+        // The polyhedron inset by the radius, whose faces, edges and corners are expanded into
+        // the flat faces, cylinders and spheres
+        let inset: Vec<(Vec3, f32)> = planes
+            .iter()
+            .map(|&(normal, distance)| (normal, distance - radius))
+            .collect();
+        let epsilon = tolerance(
+            inset
+                .iter()
+                .map(|(_, distance)| distance.abs())
+                .chain([radius]),
+        );
+        let core = plane_corners(&inset, epsilon);
+        debug_assert!(
+            core.len() >= 3,
+            "the inset polyhedron is not even a polygon"
+        );
+        let centroid = core.iter().sum::<Vec3>() / core.len() as f32;
+
+        // The corners of each face anticlockwise, and the faces around each edge and corner.
+        // Faces which the inset shrinks to an edge, such as the sides of a slab twice the
+        // radius thick, still lie between the faces either side of it. Of faces in the same
+        // plane, only the first is kept.
+        let polygons: Vec<Vec<usize>> = inset
+            .iter()
+            .enumerate()
+            .map(|(face, &(normal, distance))| {
+                let duplicate = inset[..face].iter().any(|&(other, other_distance)| {
+                    other.dot(normal) > 1.0 - 1e-6 && (other_distance - distance).abs() <= epsilon
+                });
+                if duplicate {
+                    return Vec::new();
+                }
+                let mut polygon: Vec<usize> = (0..core.len())
+                    .filter(|&i| (normal.dot(core[i]) - distance).abs() <= epsilon)
+                    .collect();
+                let centre = polygon.iter().map(|&i| core[i]).sum::<Vec3>() / polygon.len() as f32;
+                let (tangent, bitangent) = normal.any_orthonormal_pair();
+                let angle = |i: usize| {
+                    let offset = core[i] - centre;
+                    offset.dot(bitangent).atan2(offset.dot(tangent))
+                };
+                polygon.sort_by(|&a, &b| angle(a).total_cmp(&angle(b)));
+                if polygon.len() < 2 {
+                    polygon.clear();
+                }
+                polygon
+            })
+            .collect();
+        let mut edges: BTreeMap<(usize, usize), Vec<u32>> = BTreeMap::new();
+        let mut corners: Vec<Vec<u32>> = vec![Vec::new(); core.len()];
+        for (face, polygon) in polygons.iter().enumerate() {
+            for (i, &a) in polygon.iter().enumerate() {
+                let b = polygon[(i + 1) % polygon.len()];
+                edges
+                    .entry((a.min(b), a.max(b)))
+                    .or_default()
+                    .push(face as u32);
+                corners[a].push(face as u32);
+            }
+        }
+
+        let mut surface = Surface {
+            face_normals: &face_normals,
+            vertices: Vec::new(),
+            triangles: Vec::new(),
+        };
+        // The direction `j` steps of `segments` from the normal of one face to another, which
+        // is the same whichever way round the faces are given
+        let arc = |a: u32, b: u32, j: u32, segments: u32| {
+            let (a, b, j) = if a < b {
+                (a, b, j)
+            } else {
+                (b, a, segments - j)
+            };
+            slerp(
+                face_normals[a as usize],
+                face_normals[b as usize],
+                j as f32 / segments as f32,
+            )
+        };
+
+        // Flat faces
+        for (face, polygon) in polygons.iter().enumerate() {
+            let face = face as u32;
+            if polygon.is_empty() || self.options.is_face_omitted(face) {
+                continue;
+            }
+            let normal = face_normals[face as usize];
+            let fan: Vec<u32> = polygon
+                .iter()
+                .map(|&i| surface.vertex(core[i] + radius * normal, normal, &[face]))
+                .collect();
+            for i in 1..fan.len() - 1 {
+                surface.triangle([fan[0], fan[i], fan[i + 1]], &[face]);
+            }
+        }
+
+        // Cylindrical edges, with a column of vertices across each end. The faces around an
+        // edge are ordered by angle from the direction into the inset polyhedron, and each pair
+        // of neighbouring faces is joined by a cylinder.
+        for (&(start, end), faces) in &mut edges {
+            faces.sort_unstable();
+            faces.dedup();
+            debug_assert!(faces.len() >= 2, "the planes do not enclose the polyhedron");
+            let axis = (core[end] - core[start]).normalize();
+            let inward = (centroid - core[start]).reject_from_normalized(axis);
+            let across = axis.cross(inward);
+            let angle = |face: u32| {
+                let normal = face_normals[face as usize];
+                normal.dot(across).atan2(normal.dot(inward)).rem_euclid(TAU)
+            };
+            faces.sort_by(|&a, &b| angle(a).total_cmp(&angle(b)));
+            for pair in faces.windows(2) {
+                let [a, b] = [pair[0], pair[1]];
+                let columns: Vec<[u32; 2]> = (0..=subdivisions)
+                    .map(|j| {
+                        let normal = arc(a, b, j, subdivisions);
+                        [start, end]
+                            .map(|i| surface.vertex(core[i] + radius * normal, normal, pair))
+                    })
+                    .collect();
+                for columns in columns.windows(2) {
+                    let ([jj, jk], [kj, kk]) = (columns[0], columns[1]);
+                    surface.triangle([jj, kj, jk], pair);
+                    surface.triangle([kj, kk, jk], pair);
+                }
+            }
+        }
+
+        // Spherical corners, as a triangular patch between the centre of the corner and each
+        // edge around it. The rows of each patch run from the edge to the centre.
+        for (corner, faces) in corners.iter_mut().enumerate() {
+            if faces.len() < 3 {
+                continue;
+            }
+            let centre = faces
+                .iter()
+                .map(|&face| face_normals[face as usize])
+                .sum::<Vec3>()
+                .normalize();
+            let (tangent, bitangent) = centre.any_orthonormal_pair();
+            let angle = |face: u32| {
+                let normal = face_normals[face as usize];
+                normal.dot(bitangent).atan2(normal.dot(tangent))
+            };
+            faces.sort_by(|&a, &b| angle(a).total_cmp(&angle(b)));
+            let faces = faces.as_slice();
+            for (i, &a) in faces.iter().enumerate() {
+                let b = faces[(i + 1) % faces.len()];
+                let rows: Vec<Vec<u32>> = (0..=subdivisions)
+                    .map(|row| {
+                        let segments = subdivisions - row;
+                        (0..=segments)
+                            .map(|j| {
+                                let normal = match row {
+                                    0 => arc(a, b, j, segments),
+                                    row if row == subdivisions => centre,
+                                    row => slerp(
+                                        arc(a, b, j, segments),
+                                        centre,
+                                        row as f32 / subdivisions as f32,
+                                    ),
+                                };
+                                surface.vertex(core[corner] + radius * normal, normal, faces)
+                            })
+                            .collect()
+                    })
+                    .collect();
+                for pair in rows.windows(2) {
+                    let (outer, inner) = (&pair[0], &pair[1]);
+                    for j in 0..outer.len() - 1 {
+                        surface.triangle([outer[j], outer[j + 1], inner[j]], faces);
+                        if j + 1 < inner.len() {
+                            surface.triangle([outer[j + 1], inner[j + 1], inner[j]], faces);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Texture coordinates are projected onto the plane of each face, spanning the extent
+        // of the shape across it
+        #[cfg(feature = "uvf")]
+        let projections: Vec<(Vec3, Vec3, Vec2, Vec2)> = face_normals
+            .iter()
+            .map(|normal| {
+                let (tangent, bitangent) = normal.any_orthonormal_pair();
+                let (min, max) = core.iter().fold(
+                    (Vec2::INFINITY, Vec2::NEG_INFINITY),
+                    |(min, max), corner| {
+                        let p = Vec2::new(corner.dot(tangent), corner.dot(bitangent));
+                        (min.min(p), max.max(p))
+                    },
+                );
+                (tangent, bitangent, min - radius, max - min + 2.0 * radius)
+            })
+            .collect();
+
+        // Generate vertices, split between faces if needed, and indices
+        let split_faces = self.options.is_split_faces();
+        let facing = if self.options.inverted { -1.0 } else { 1.0 };
+        let mut positions: Vec<[f32; 3]> = Vec::new();
+        let mut normals: Vec<[f32; 3]> = Vec::new();
+        #[cfg(feature = "uvf")]
+        let mut uvs: Vec<[f32; 2]> = Vec::new();
+        #[cfg(feature = "uvf")]
+        let mut faces: Vec<u32> = Vec::new();
+        // Vertices along the seams between the faces, edges and corners are shared by position
+        // and normal, and between faces too unless they are split
+        let mut split = HashMap::new();
+        let mut indices: Vec<u32> = Vec::with_capacity(3 * surface.triangles.len());
+        for &(triangle, triangle_face) in &surface.triangles {
+            for vertex in triangle {
+                let (pos, normal, nearest_face) = surface.vertices[vertex as usize];
+                let face = if split_faces {
+                    triangle_face
+                } else {
+                    nearest_face
+                };
+                let key = (
+                    pos.to_array().map(f32::to_bits),
+                    normal.to_array().map(f32::to_bits),
+                    split_faces.then_some(face),
+                );
+                let index = *split.entry(key).or_insert_with(|| {
+                    positions.push(pos.to_array());
+                    normals.push((facing * normal).to_array());
+                    #[cfg(feature = "uvf")]
+                    if self.options.is_generate_uv() {
+                        let (tangent, bitangent, min, size) = projections[face as usize];
+                        let uv = (Vec2::new(pos.dot(tangent), pos.dot(bitangent)) - min) / size;
+                        let uv = if self.options.inverted {
+                            Vec2::new(1.0 - uv.x, 1.0 - uv.y)
+                        } else {
+                            Vec2::new(uv.x, 1.0 - uv.y)
+                        };
+                        uvs.push(uv.to_array());
+                    }
+                    #[cfg(feature = "uvf")]
+                    if self.options.is_generate_face() {
+                        faces.push(face);
+                    }
+                    positions.len() as u32 - 1
+                });
+                indices.push(index);
+            }
+        }
+        if self.options.is_reversed(self.options.inverted) {
+            for triangle in indices.chunks_exact_mut(3) {
+                triangle.swap(1, 2);
+            }
+        }
+
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        mesh.insert_indices(Indices::U32(indices));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        #[cfg(feature = "uvf")]
+        if self.options.is_generate_uv() {
+            mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        }
+        #[cfg(feature = "uvf")]
+        if self.options.is_generate_face() {
+            mesh.insert_attribute(ATTRIBUTE_FACE, faces);
+        }
+        mesh
+    }
+}
+
+impl RoundedConvexMeshBuilder {
+    /// Sets the number of subdivisions.
+    pub fn with_subdivisions(self, subdivisions: usize) -> Self {
+        RoundedConvexMeshBuilder {
+            subdivisions,
+            ..self
+        }
+    }
+
+    /// Sets the mesh generation options.
+    pub fn with_options(self, options: RoundedBoxMeshOptions) -> Self {
+        RoundedConvexMeshBuilder { options, ..self }
+    }
+
+    /// Enable generating [`Mesh::ATTRIBUTE_UV_0`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub fn with_uv(mut self) -> Self {
+        self.options = self.options.with_uv();
+        self
+    }
+
+    /// Enable generating [`ATTRIBUTE_FACE`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub fn with_face(mut self) -> Self {
+        self.options = self.options.with_face();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    #[cfg(feature = "uvf")]
+    use crate::ATTRIBUTE_FACE;
+    use bevy::render::mesh::VertexAttributeValues;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_rounded_convex() {
+        let cube = [-0.5, 0.5].into_iter().flat_map(|x| {
+            [-0.5, 0.5]
+                .into_iter()
+                .flat_map(move |y| [-0.5, 0.5].map(|z| Vec3::new(x, y, z)))
+        });
+        let octahedron = [-1.0, 1.0].into_iter().flat_map(|x| {
+            [-1.0, 1.0]
+                .into_iter()
+                .flat_map(move |y| [-1.0, 1.0].map(|z| (Vec3::new(x, y, z), 0.5)))
+        });
+        let wedge = [
+            Vec3::new(-0.5, -0.5, -0.5),
+            Vec3::new(0.5, -0.5, -0.5),
+            Vec3::new(-0.5, 0.5, -0.5),
+            Vec3::new(0.5, 0.5, -0.5),
+            Vec3::new(-0.5, -0.5, 0.5),
+            Vec3::new(0.5, -0.5, 0.5),
+        ];
+        // Interior and repeated points, and a plane which does not touch the polyhedron
+        let cube_points = cube.clone().chain([Vec3::ZERO, Vec3::splat(0.5)]);
+        let extra_plane = [(Vec3::new(0.0, 0.0, 2.0), 3.0)];
+        // A plane given twice, and a slab which the inset shrinks to a square
+        let cube_planes = [Vec3::X, Vec3::Y, Vec3::Z]
+            .into_iter()
+            .flat_map(|axis| [(axis, 0.5), (-axis, 0.5)]);
+        let repeated_plane = [(Vec3::new(2.0, 0.0, 0.0), 1.0)];
+        let slab = cube.clone().map(|p| p * Vec3::new(1.0, 1.0, 0.4));
+        for (convex, faces) in [
+            (RoundedConvex::default(), 4),
+            (RoundedConvex::from_vertices(cube_points, 0.2), 6),
+            (
+                RoundedConvex::from_planes(octahedron.chain(extra_plane), 0.1),
+                8,
+            ),
+            (RoundedConvex::from_vertices(wedge, 0.1), 5),
+            (RoundedConvex::from_vertices(wedge, 0.0), 5),
+            (
+                RoundedConvex::from_planes(cube_planes.chain(repeated_plane), 0.1),
+                7,
+            ),
+            (RoundedConvex::from_vertices(slab, 0.2), 6),
+        ] {
+            let vertices = convex.vertices();
+            for subdivisions in 1..=5 {
+                for options in MESH_OPTIONS {
+                    println!(
+                        "convex={:?} subdivisions={} options={:?}",
+                        convex, subdivisions, options
+                    );
+                    let mesh = convex
+                        .mesh()
+                        .with_subdivisions(subdivisions)
+                        .with_options(options)
+                        .build();
+                    assert_no_degenerates(&mesh);
+                    assert_no_duplicates(&mesh);
+                    assert_unit_normals(&mesh);
+                    assert_outward_normals(&mesh);
+                    assert_closed(&mesh);
+
+                    // Every vertex lies within the polyhedron, and at the radius from the inset
+                    // polyhedron along its normal
+                    let pos = vertex_positions(&mesh);
+                    let normals = vertex_normals(&mesh);
+                    let mut touched = HashSet::new();
+                    for (&p, &normal) in pos.iter().zip(normals) {
+                        let (p, normal) = (Vec3::from(p), Vec3::from(normal));
+                        let core = p - convex.radius * normal;
+                        for (face, &(plane_normal, distance)) in convex.planes.iter().enumerate() {
+                            assert!(plane_normal.dot(p) <= distance + 1e-5, "p={}", p);
+                            let height = plane_normal.dot(core) - distance + convex.radius;
+                            assert!(height <= 1e-5, "p={} normal={}", p, normal);
+                            if (plane_normal.dot(p) - distance).abs() < 1e-5 {
+                                touched.insert(face);
+                            }
+                        }
+                    }
+                    assert_eq!(touched.len(), faces);
+                    // Unless the faces are split, the seams between faces, edges and corners
+                    // share their vertices
+                    if !options.is_split_faces() && convex.radius > 0.0 {
+                        let unique: HashSet<[u32; 3]> =
+                            pos.iter().map(|p| p.map(f32::to_bits)).collect();
+                        assert_eq!(unique.len(), pos.len());
+                    }
+                    // Sharp polyhedra only have vertices at their corners
+                    if convex.radius == 0.0 {
+                        for &p in pos {
+                            let p = Vec3::from(p);
+                            assert!(vertices.iter().any(|v| v.distance(p) < 1e-5), "p={}", p);
+                        }
+                    }
+
+                    #[cfg(feature = "uvf")]
+                    if let Some(VertexAttributeValues::Uint32(mesh_faces)) =
+                        mesh.attribute(ATTRIBUTE_FACE)
+                    {
+                        assert!(mesh_faces
+                            .iter()
+                            .all(|&face| face < convex.planes.len() as u32));
+                    }
+                    if let Some(VertexAttributeValues::Float32x2(uvs)) =
+                        mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+                    {
+                        for &uv in uvs {
+                            let uv = Vec2::from(uv);
+                            assert!(uv.cmpge(Vec2::splat(-1e-5)).all(), "uv={}", uv);
+                            assert!(uv.cmple(Vec2::splat(1.0 + 1e-5)).all(), "uv={}", uv);
+                        }
+                    }
+                }
+            }
+        }
+
+        // A rounded cube has the bounds of the cube
+        let rounded_cube = RoundedConvex::from_vertices(cube, 0.2);
+        assert_eq!(rounded_cube.vertices().len(), 8);
+        assert_bounds(&rounded_cube.mesh().build(), Vec3::ONE);
+    }
+}
@@ -0,0 +1,205 @@
+use std::f32::consts::{FRAC_PI_4, TAU};
+
+use bevy::prelude::*;
+
+use crate::{
+    extrude::{edge_rings, Caps, Column, Extrusion},
+    RoundedBoxMeshOptions,
+};
+
+/// A cylinder with rounded rims, such as a coin, a button or a puck.
+///
+/// The axis of the cylinder is parallel to Z. For [`ATTRIBUTE_FACE`](crate::ATTRIBUTE_FACE),
+/// the top is numbered 0, the side 1 and the bottom 2.
+#[derive(Copy, Clone, Debug)]
+pub struct RoundedCylinder {
+    /// The radius of the cylinder.
+    pub radius: f32,
+    /// The height of the cylinder.
+    pub height: f32,
+    /// The radius of the rims, which must not exceed the radius or half the height.
+    pub fillet_radius: f32,
+}
+
+impl RoundedCylinder {
+    /// Creates a rounded cylinder.
+    pub const fn new(radius: f32, height: f32, fillet_radius: f32) -> Self {
+        Self {
+            radius,
+            height,
+            fillet_radius,
+        }
+    }
+}
+
+impl Default for RoundedCylinder {
+    fn default() -> Self {
+        Self::new(0.5, 1.0, 0.1)
+    }
+}
+
+impl Meshable for RoundedCylinder {
+    type Output = RoundedCylinderMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        RoundedCylinderMeshBuilder {
+            cylinder: *self,
+            subdivisions: 4,
+            options: RoundedBoxMeshOptions::DEFAULT,
+        }
+    }
+}
+
+impl From<RoundedCylinder> for Mesh {
+    fn from(value: RoundedCylinder) -> Self {
+        value.mesh().build()
+    }
+}
+
+/// A builder used for creating a [`Mesh`] with a [`RoundedCylinder`] shape.
+#[derive(Copy, Clone, Debug)]
+pub struct RoundedCylinderMeshBuilder {
+    /// The [`RoundedCylinder`] shape.
+    pub cylinder: RoundedCylinder,
+    /// The number of segments in each rim, and in each quarter of the circumference.
+    pub subdivisions: usize,
+    /// Mesh generation options.
+    pub options: RoundedBoxMeshOptions,
+}
+
+impl Default for RoundedCylinderMeshBuilder {
+    fn default() -> Self {
+        RoundedCylinder::default().mesh()
+    }
+}
+
+impl MeshBuilder for RoundedCylinderMeshBuilder {
+    fn build(&self) -> Mesh {
+        debug_assert!(self.subdivisions > 0);
+        let split_faces = self.options.is_split_faces();
+        let subdivisions = if split_faces {
+            self.subdivisions + self.subdivisions % 2
+        } else {
+            self.subdivisions
+        } as u32;
+        let RoundedCylinder {
+            radius,
+            height,
+            fillet_radius,
+        } = self.cylinder;
+        debug_assert!(fillet_radius <= radius && 2.0 * fillet_radius <= height);
+        let cap_radius = radius - fillet_radius;
+        let core_height = height / 2.0 - fillet_radius;
+        let (rings, side_length) = edge_rings(
+            subdivisions,
+            fillet_radius,
+            core_height,
+            [0, 2],
+            split_faces,
+        );
+
+        // The sectors wrap around with a seam where texture coordinates need one, which has the
+        // same positions as the first sector
+        let sectors = 4 * subdivisions;
+        let seam = if self.options.is_generate_uv() { 1 } else { 0 };
+        let columns = (0..sectors + seam)
+            .map(|sector| {
+                let dir = Vec2::from_angle((sector % sectors) as f32 * TAU / sectors as f32);
+                Column {
+                    corner: cap_radius * dir,
+                    dir,
+                    face: 1,
+                    u: sector as f32 / sectors as f32,
+                }
+            })
+            .collect();
+        let extrusion = Extrusion {
+            rings,
+            side_length,
+            columns,
+            caps: Caps::Fan,
+            curved_sides: true,
+        };
+        // The top and bottom are mapped by the distance along the surface from their centres
+        let cap_length = cap_radius + FRAC_PI_4 * fillet_radius;
+        extrusion.build(&self.options, |pos, ring| {
+            0.5 + 0.5 * (cap_length + ring.length) / cap_length * pos.normalize_or_zero()
+        })
+    }
+}
+
+impl RoundedCylinderMeshBuilder {
+    /// Sets the number of subdivisions.
+    pub const fn with_subdivisions(self, subdivisions: usize) -> Self {
+        RoundedCylinderMeshBuilder {
+            subdivisions,
+            ..self
+        }
+    }
+
+    /// Sets the mesh generation options.
+    pub const fn with_options(self, options: RoundedBoxMeshOptions) -> Self {
+        RoundedCylinderMeshBuilder { options, ..self }
+    }
+
+    /// Enable generating [`Mesh::ATTRIBUTE_UV_0`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub const fn with_uv(mut self) -> Self {
+        self.options = self.options.with_uv();
+        self
+    }
+
+    /// Enable generating [`ATTRIBUTE_FACE`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub const fn with_face(mut self) -> Self {
+        self.options = self.options.with_face();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    #[cfg(feature = "uvf")]
+    use crate::ATTRIBUTE_FACE;
+    use bevy::render::mesh::VertexAttributeValues;
+
+    #[test]
+    fn test_rounded_cylinder() {
+        for subdivisions in 1..=6 {
+            for options in MESH_OPTIONS {
+                println!("subdivisions={} options={:?}", subdivisions, options);
+                // Rounded, sharp and fully rounded rims
+                for fillet_radius in [0.1, 0.0, 0.5] {
+                    let cylinder = RoundedCylinder::new(0.5, 1.0, fillet_radius);
+                    let mesh = cylinder
+                        .mesh()
+                        .with_subdivisions(subdivisions)
+                        .with_options(options)
+                        .build();
+                    assert_no_degenerates(&mesh);
+                    assert_no_duplicates(&mesh);
+                    assert_bounds(&mesh, Vec3::ONE);
+                    assert_unit_normals(&mesh);
+                    assert_outward_normals(&mesh);
+                    assert_closed(&mesh);
+                    #[cfg(feature = "uvf")]
+                    if let Some(VertexAttributeValues::Uint32(faces)) =
+                        mesh.attribute(ATTRIBUTE_FACE)
+                    {
+                        assert!(faces.iter().all(|&face| face < 3));
+                    }
+                    if let Some(VertexAttributeValues::Float32x2(uvs)) =
+                        mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+                    {
+                        for &uv in uvs {
+                            assert!(Vec2::from(uv).cmpge(Vec2::ZERO).all(), "uv={:?}", uv);
+                            assert!(Vec2::from(uv).cmple(Vec2::ONE).all(), "uv={:?}", uv);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use bevy::prelude::*;
+
+use crate::{
+    extrude::{edge_rings, fillet_columns, Caps, Extrusion},
+    RoundedBoxMeshOptions,
+};
+
+/// A simple polygon with filleted corners, extruded along Z with rounded top and bottom edges,
+/// such as an L-shaped countertop or a U-shaped frame.
+///
+/// Both the convex and the concave corners of the outline are filleted. For
+/// [`ATTRIBUTE_FACE`](crate::ATTRIBUTE_FACE), the top is numbered 0 and the bottom n + 1. The
+/// sides are numbered 1 to n anticlockwise around Z, starting from the side which leaves the
+/// first point of the outline.
+#[derive(Clone, Debug)]
+pub struct RoundedExtrusion {
+    /// The corners of the unrounded polygon, in either direction around it.
+    pub outline: Vec<Vec2>,
+    /// The height of the extrusion, which is centred on the origin.
+    pub height: f32,
+    /// The radius of the fillets at the corners of the outline, which must leave some of each
+    /// side straight.
+    pub corner_radius: f32,
+    /// The radius of the top and bottom edges, which must not exceed the corner radius or half
+    /// the height.
+    pub edge_radius: f32,
+}
+
+impl RoundedExtrusion {
+    /// Creates an extrusion whose corners and edges have the same radius.
+    pub fn new(outline: impl IntoIterator<Item = Vec2>, height: f32, radius: f32) -> Self {
+        Self {
+            outline: outline.into_iter().collect(),
+            height,
+            corner_radius: radius,
+            edge_radius: radius,
+        }
+    }
+}
+
+impl Default for RoundedExtrusion {
+    fn default() -> Self {
+        let l_shape = [
+            Vec2::new(-0.5, -0.5),
+            Vec2::new(0.5, -0.5),
+            Vec2::new(0.5, 0.0),
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 0.5),
+            Vec2::new(-0.5, 0.5),
+        ];
+        Self::new(l_shape, 0.2, 0.05)
+    }
+}
+
+impl Meshable for RoundedExtrusion {
+    type Output = RoundedExtrusionMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        RoundedExtrusionMeshBuilder {
+            extrusion: self.clone(),
+            subdivisions: 4,
+            options: RoundedBoxMeshOptions::DEFAULT,
+        }
+    }
+}
+
+impl From<RoundedExtrusion> for Mesh {
+    fn from(value: RoundedExtrusion) -> Self {
+        value.mesh().build()
+    }
+}
+
+/// A builder used for creating a [`Mesh`] with a [`RoundedExtrusion`] shape.
+#[derive(Clone, Debug)]
+pub struct RoundedExtrusionMeshBuilder {
+    /// The [`RoundedExtrusion`] shape.
+    pub extrusion: RoundedExtrusion,
+    /// The number of segments in each corner, and in each rounded edge of the top or bottom.
+    pub subdivisions: usize,
+    /// Mesh generation options.
+    pub options: RoundedBoxMeshOptions,
+}
+
+impl Default for RoundedExtrusionMeshBuilder {
+    fn default() -> Self {
+        RoundedExtrusion::default().mesh()
+    }
+}
+
+impl MeshBuilder for RoundedExtrusionMeshBuilder {
+    fn build(&self) -> Mesh {
+        debug_assert!(self.subdivisions > 0);
+        let split_faces = self.options.is_split_faces();
+        let subdivisions = if split_faces {
+            self.subdivisions + self.subdivisions % 2
+        } else {
+            self.subdivisions
+        } as u32;
+        let RoundedExtrusion {
+            height,
+            corner_radius,
+            edge_radius,
+            ..
+        } = self.extrusion;
+        debug_assert!(edge_radius <= corner_radius && 2.0 * edge_radius <= height);
+
+        // Wind the outline anticlockwise, keeping the first point first
+        let mut outline = self.extrusion.outline.clone();
+        let sides = outline.len() as u32;
+        debug_assert!(sides >= 3);
+        let area: f32 = (0..outline.len())
+            .map(|i| outline[i].perp_dot(outline[(i + 1) % outline.len()]))
+            .sum();
+        if area < 0.0 {
+            outline[1..].reverse();
+        }
+
+        let core_height = height / 2.0 - edge_radius;
+        let (rings, side_length) = edge_rings(
+            subdivisions,
+            edge_radius,
+            core_height,
+            [0, sides + 1],
+            split_faces,
+        );
+        let extrusion = Extrusion {
+            rings,
+            side_length,
+            columns: fillet_columns(
+                &outline,
+                corner_radius,
+                edge_radius,
+                subdivisions,
+                split_faces,
+            ),
+            caps: Caps::Triangulated,
+            curved_sides: false,
+        };
+        let (min, max) = outline
+            .iter()
+            .fold((Vec2::INFINITY, Vec2::NEG_INFINITY), |(min, max), &p| {
+                (min.min(p), max.max(p))
+            });
+        extrusion.build(&self.options, |pos, _| (pos - min) / (max - min))
+    }
+}
+
+impl RoundedExtrusionMeshBuilder {
+    /// Sets the number of subdivisions.
+    pub fn with_subdivisions(self, subdivisions: usize) -> Self {
+        RoundedExtrusionMeshBuilder {
+            subdivisions,
+            ..self
+        }
+    }
+
+    /// Sets the mesh generation options.
+    pub fn with_options(self, options: RoundedBoxMeshOptions) -> Self {
+        RoundedExtrusionMeshBuilder { options, ..self }
+    }
+
+    /// Enable generating [`Mesh::ATTRIBUTE_UV_0`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub fn with_uv(mut self) -> Self {
+        self.options = self.options.with_uv();
+        self
+    }
+
+    /// Enable generating [`ATTRIBUTE_FACE`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub fn with_face(mut self) -> Self {
+        self.options = self.options.with_face();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "uvf")]
+    use crate::ATTRIBUTE_FACE;
+    use bevy::render::mesh::VertexAttributeValues;
+    use std::f32::consts::PI;
+
+    use crate::test_helpers::*;
+
+    #[test]
+    fn test_rounded_extrusion() {
+        let l_shape = [
+            Vec2::new(-1.0, -1.0),
+            Vec2::new(1.0, -1.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 1.0),
+            Vec2::new(-1.0, 1.0),
+        ];
+        let u_shape = [
+            Vec2::new(-1.0, -1.0),
+            Vec2::new(1.0, -1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.5, 1.0),
+            Vec2::new(0.5, -0.5),
+            Vec2::new(-0.5, -0.5),
+            Vec2::new(-0.5, 1.0),
+            Vec2::new(-1.0, 1.0),
+        ];
+        // Clockwise, and with a straight corner
+        let square = [
+            Vec2::new(-1.0, -1.0),
+            Vec2::new(-1.0, 1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, -1.0),
+        ];
+        let mut extrusions = vec![
+            (RoundedExtrusion::new(l_shape, 0.5, 0.2), 3.0, 4),
+            (RoundedExtrusion::new(u_shape, 0.5, 0.1), 2.5, 4),
+            (RoundedExtrusion::new(square, 0.5, 0.2), 4.0, 4),
+            (RoundedExtrusion::new(l_shape, 0.5, 0.0), 3.0, 4),
+        ];
+        extrusions.push((
+            RoundedExtrusion {
+                edge_radius: 0.1,
+                ..extrusions[0].0.clone()
+            },
+            3.0,
+            4,
+        ));
+        for (extrusion, _, _) in &extrusions {
+            for subdivisions in 1..=6 {
+                for options in MESH_OPTIONS {
+                    println!(
+                        "extrusion={:?} subdivisions={} options={:?}",
+                        extrusion, subdivisions, options
+                    );
+                    let mesh = extrusion
+                        .mesh()
+                        .with_subdivisions(subdivisions)
+                        .with_options(options)
+                        .build();
+                    assert_no_degenerates(&mesh);
+                    assert_no_duplicates(&mesh);
+                    assert_bounds(&mesh, Vec3::new(2.0, 2.0, 0.5));
+                    assert_unit_normals(&mesh);
+                    assert_outward_normals(&mesh);
+                    assert_closed(&mesh);
+                    #[cfg(feature = "uvf")]
+                    if let Some(VertexAttributeValues::Uint32(faces)) =
+                        mesh.attribute(ATTRIBUTE_FACE)
+                    {
+                        let sides = extrusion.outline.len() as u32;
+                        assert!(faces.iter().all(|&face| face < sides + 2));
+                    }
+                    if let Some(VertexAttributeValues::Float32x2(uvs)) =
+                        mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+                    {
+                        for &uv in uvs {
+                            let uv = Vec2::from(uv);
+                            assert!(uv.cmpge(Vec2::splat(-1e-5)).all(), "uv={}", uv);
+                            assert!(uv.cmple(Vec2::splat(1.0 + 1e-5)).all(), "uv={}", uv);
+                        }
+                    }
+                }
+            }
+        }
+
+        // The top covers the area of the outline, less the convex fillets and plus the concave
+        // ones, of which there are four more
+        for (extrusion, area, fillets) in &extrusions {
+            let mesh = extrusion.mesh().with_subdivisions(32).build();
+            let pos = vertex_positions(&mesh);
+            let indices = mesh.indices().unwrap();
+            let mut top = 0.0;
+            let mut it = indices.iter();
+            while let (Some(a), Some(b), Some(c)) = (it.next(), it.next(), it.next()) {
+                let [pa, pb, pc] = [a, b, c].map(|i| Vec3::from(pos[i]));
+                top += (0.5 * (pb - pa).cross(pc - pa).z).max(0.0);
+            }
+            let fillet = (4.0 - PI) / 4.0 * extrusion.corner_radius.powi(2);
+            let expected = area - *fillets as f32 * fillet;
+            assert!(
+                (top - expected).abs() < 1e-3,
+                "top={} expected={}",
+                top,
+                expected
+            );
+        }
+    }
+}
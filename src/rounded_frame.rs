@@ -0,0 +1,361 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+    },
+};
+
+#[cfg(feature = "uvf")]
+use crate::ATTRIBUTE_FACE;
+use crate::{BoxEdges, CornerProfile, RoundedBoxMeshOptions, XYQuarter};
+
+/// The twelve edges of a box as tubes joined by spherical corners, such as a display case or a
+/// selection cage.
+///
+/// The outside of the frame is that of a [`RoundedBox`](crate::RoundedBox) of the same size
+/// whose radius is the radius of the tubes, so the axes of the tubes run along the edges of a box
+/// smaller by twice the radius. Where the tubes meet on the inside of a corner, they are mitred.
+///
+/// For [`ATTRIBUTE_FACE`](crate::ATTRIBUTE_FACE), the tubes are numbered from 0 to 11 in the
+/// order of [`EdgeRadii`](crate::EdgeRadii), and the corners from 12 to 19, adding 1, 2 and 4
+/// for the corners on the positive sides of X, Y and Z.
+#[derive(Copy, Clone, Debug)]
+pub struct RoundedBoxFrame {
+    /// The dimensions of the frame.
+    pub size: Vec3,
+    /// The radius of the tubes, which must not exceed a quarter of any dimension.
+    pub radius: f32,
+}
+
+impl RoundedBoxFrame {
+    /// Creates a frame.
+    pub const fn new(size: Vec3, radius: f32) -> Self {
+        Self { size, radius }
+    }
+}
+
+impl Default for RoundedBoxFrame {
+    fn default() -> Self {
+        Self::new(Vec3::ONE, 0.05)
+    }
+}
+
+impl Meshable for RoundedBoxFrame {
+    type Output = RoundedBoxFrameMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        RoundedBoxFrameMeshBuilder {
+            frame: *self,
+            subdivisions: 4,
+            options: RoundedBoxMeshOptions::DEFAULT,
+        }
+    }
+}
+
+impl From<RoundedBoxFrame> for Mesh {
+    fn from(value: RoundedBoxFrame) -> Self {
+        value.mesh().build()
+    }
+}
+
+/// A builder used for creating a [`Mesh`] with a [`RoundedBoxFrame`] shape.
+#[derive(Copy, Clone, Debug)]
+pub struct RoundedBoxFrameMeshBuilder {
+    /// The [`RoundedBoxFrame`] shape.
+    pub frame: RoundedBoxFrame,
+    /// The number of sectors and stacks in each corner, and of segments in each quarter of the
+    /// circumference of the tubes. It is rounded up to an even number, so that the tubes meet
+    /// at the diagonals of the corners.
+    pub subdivisions: usize,
+    /// Mesh generation options.
+    ///
+    /// The texture coordinates of the tubes run around them and along them, and the faces are
+    /// not omitted.
+    pub options: RoundedBoxMeshOptions,
+}
+
+impl Default for RoundedBoxFrameMeshBuilder {
+    fn default() -> Self {
+        RoundedBoxFrame::default().mesh()
+    }
+}
+
+impl MeshBuilder for RoundedBoxFrameMeshBuilder {
+    fn build(&self) -> Mesh {
+        debug_assert!(self.subdivisions > 0);
+        let RoundedBoxFrame { size, radius } = self.frame;
+        debug_assert!(radius > 0.0 && 4.0 * radius <= size.min_element());
+        let subdivisions = (self.subdivisions + self.subdivisions % 2) as u32;
+        let edges = BoxEdges {
+            half_size: size / 2.0,
+            radii: [[Vec3::splat(radius); 4]; 3],
+        };
+        let axis_corner = edges.half_size - radius;
+
+        // The cosines of the angles across a quarter circle, whose sines are the same values in
+        // reverse, so that the tubes and corners have exactly the same positions along their
+        // seams
+        let cosines: Vec<f32> = (0..=subdivisions)
+            .map(|i| match i {
+                0 => 1.0,
+                i if i == subdivisions => 0.0,
+                i => (i as f32 * FRAC_PI_2 / subdivisions as f32).cos(),
+            })
+            .collect();
+        let unit = |i: u32| {
+            let (quarter, i) = (i / subdivisions % 4, (i % subdivisions) as usize);
+            let (cos, sin) = (cosines[i], cosines[subdivisions as usize - i]);
+            match quarter {
+                0 => Vec2::new(cos, sin),
+                1 => Vec2::new(-sin, cos),
+                2 => Vec2::new(-cos, -sin),
+                _ => Vec2::new(sin, -cos),
+            }
+        };
+        // The position and normal of the point of a corner with the given normal in its octant
+        let outer = |corner: Vec3, dir: Vec3| {
+            let (inset, normal) = edges.corner_point(corner, dir, &CornerProfile::Circular);
+            ((edges.half_size - inset) * corner, normal * corner)
+        };
+
+        let seam = if self.options.is_generate_uv() { 1 } else { 0 };
+        let facing = if self.options.inverted { -1.0 } else { 1.0 };
+        let mut positions: Vec<[f32; 3]> = Vec::new();
+        let mut normals: Vec<[f32; 3]> = Vec::new();
+        #[cfg(feature = "uvf")]
+        let mut uvs: Vec<[f32; 2]> = Vec::new();
+        #[cfg(feature = "uvf")]
+        let mut faces: Vec<u32> = Vec::new();
+        let mut push_vertex = |pos: Vec3, normal: Vec3, uv: Vec2, face: u32| {
+            positions.push(pos.to_array());
+            normals.push((facing * normal).to_array());
+            #[cfg(feature = "uvf")]
+            if self.options.is_generate_uv() {
+                let uv = if self.options.inverted {
+                    Vec2::new(1.0 - uv.x, uv.y)
+                } else {
+                    uv
+                };
+                uvs.push(uv.to_array());
+            }
+            #[cfg(feature = "uvf")]
+            if self.options.is_generate_face() {
+                faces.push(face);
+            }
+            #[cfg(not(feature = "uvf"))]
+            let _ = (uv, face);
+        };
+        let mut quads: Vec<[u32; 4]> = Vec::new();
+
+        // Tubes, with a ring of vertices at either end. The rings follow the corners where they
+        // face outwards, and the mitres between the tubes where they face inwards.
+        let sectors = 4 * subdivisions;
+        let ring = sectors + seam;
+        let mut first = 0;
+        for axis in 0..3 {
+            let [u_axis, v_axis] = [(axis + 1) % 3, (axis + 2) % 3];
+            for quarter in 0..4 {
+                let coords = XYQuarter(quarter).coords();
+                for end in [-1.0, 1.0] {
+                    let mut corner = Vec3::ZERO;
+                    corner[axis] = end;
+                    corner[u_axis] = coords.x;
+                    corner[v_axis] = coords.y;
+                    for sector in 0..ring {
+                        let unit = unit(sector);
+                        let mut normal = Vec3::ZERO;
+                        normal[u_axis] = unit.x;
+                        normal[v_axis] = unit.y;
+                        let dir = normal * corner;
+                        let mitre = -dir.min_element();
+                        let pos = if mitre > 0.0 {
+                            let mut offset = dir;
+                            offset[axis] = -mitre;
+                            (axis_corner + radius * offset) * corner
+                        } else {
+                            outer(corner, dir).0
+                        };
+                        let along = (pos[axis] + axis_corner[axis]) / (2.0 * axis_corner[axis]);
+                        let uv = Vec2::new(sector as f32 / sectors as f32, along);
+                        push_vertex(pos, normal, uv, 4 * axis as u32 + quarter);
+                    }
+                }
+                for sector in 0..sectors {
+                    let next = (sector + 1) % ring;
+                    quads.push([
+                        first + sector,
+                        first + next,
+                        first + ring + sector,
+                        first + ring + next,
+                    ]);
+                }
+                first += 2 * ring;
+            }
+        }
+
+        // Corners, as octants of spheres between the tubes
+        let stride = subdivisions + 1;
+        for octant in 0..8 {
+            let corner = Vec3::new(
+                if octant & 1 != 0 { 1.0 } else { -1.0 },
+                if octant & 2 != 0 { 1.0 } else { -1.0 },
+                if octant & 4 != 0 { 1.0 } else { -1.0 },
+            );
+            for stack in 0..=subdivisions as usize {
+                for sector in 0..=subdivisions as usize {
+                    let (stack_cos, stack_sin) =
+                        (cosines[stack], cosines[subdivisions as usize - stack]);
+                    let (sector_cos, sector_sin) =
+                        (cosines[sector], cosines[subdivisions as usize - sector]);
+                    let dir = Vec3::new(stack_cos * sector_cos, stack_cos * sector_sin, stack_sin);
+                    let (pos, normal) = outer(corner, dir);
+                    let uv = Vec2::new(sector as f32, stack as f32) / subdivisions as f32;
+                    push_vertex(pos, normal, uv, 12 + octant);
+                }
+            }
+            for stack in 0..subdivisions {
+                for sector in 0..subdivisions {
+                    let index = |stack: u32, sector: u32| first + stack * stride + sector;
+                    quads.push([
+                        index(stack, sector),
+                        index(stack + 1, sector),
+                        index(stack, sector + 1),
+                        index(stack + 1, sector + 1),
+                    ]);
+                }
+            }
+            first += stride * stride;
+        }
+
+        // Generate indices, facing outwards in each octant and excluding the degenerate
+        // triangles at the poles of the corners
+        let mut indices: Vec<u32> = Vec::with_capacity(6 * quads.len());
+        for [jj, kj, jk, kk] in quads {
+            for triangle in [[jj, kj, jk], [kj, kk, jk]] {
+                let [a, b, c] = triangle.map(|i| Vec3::from(positions[i as usize]));
+                if a == b || b == c || c == a {
+                    continue;
+                }
+                let normal = Vec3::from(normals[triangle[0] as usize]);
+                if (b - a).cross(c - a).dot(facing * normal) < 0.0 {
+                    indices.extend([triangle[0], triangle[2], triangle[1]]);
+                } else {
+                    indices.extend(triangle);
+                }
+            }
+        }
+        if self.options.is_reversed(self.options.inverted) {
+            for triangle in indices.chunks_exact_mut(3) {
+                triangle.swap(1, 2);
+            }
+        }
+
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        mesh.insert_indices(Indices::U32(indices));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        #[cfg(feature = "uvf")]
+        if self.options.is_generate_uv() {
+            mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        }
+        #[cfg(feature = "uvf")]
+        if self.options.is_generate_face() {
+            mesh.insert_attribute(ATTRIBUTE_FACE, faces);
+        }
+        mesh
+    }
+}
+
+impl RoundedBoxFrameMeshBuilder {
+    /// Sets the number of subdivisions.
+    pub const fn with_subdivisions(self, subdivisions: usize) -> Self {
+        RoundedBoxFrameMeshBuilder {
+            subdivisions,
+            ..self
+        }
+    }
+
+    /// Sets the mesh generation options.
+    pub const fn with_options(self, options: RoundedBoxMeshOptions) -> Self {
+        RoundedBoxFrameMeshBuilder { options, ..self }
+    }
+
+    /// Enable generating [`Mesh::ATTRIBUTE_UV_0`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub const fn with_uv(mut self) -> Self {
+        self.options = self.options.with_uv();
+        self
+    }
+
+    /// Enable generating [`ATTRIBUTE_FACE`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub const fn with_face(mut self) -> Self {
+        self.options = self.options.with_face();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    #[cfg(feature = "uvf")]
+    use crate::ATTRIBUTE_FACE;
+    use bevy::render::mesh::VertexAttributeValues;
+
+    #[test]
+    fn test_rounded_box_frame() {
+        let frame = RoundedBoxFrame::new(Vec3::new(1.0, 2.0, 3.0), 0.1);
+        let axis_half_size = frame.size / 2.0 - frame.radius;
+        for subdivisions in 1..=6 {
+            for options in MESH_OPTIONS {
+                println!("subdivisions={} options={:?}", subdivisions, options);
+                let mesh = frame
+                    .mesh()
+                    .with_subdivisions(subdivisions)
+                    .with_options(options)
+                    .build();
+                assert_no_degenerates(&mesh);
+                assert_no_duplicates(&mesh);
+                assert_bounds(&mesh, frame.size);
+                assert_unit_normals(&mesh);
+                assert_outward_normals(&mesh);
+                assert_closed(&mesh);
+
+                // Every vertex lies on a tube around an edge of the smaller box
+                let pos = vertex_positions(&mesh);
+                for &p in pos {
+                    let p = Vec3::from(p);
+                    let distance = (0..3)
+                        .map(|axis| {
+                            let mut nearest = p.signum() * axis_half_size;
+                            nearest[axis] =
+                                p[axis].clamp(-axis_half_size[axis], axis_half_size[axis]);
+                            p.distance(nearest)
+                        })
+                        .fold(f32::MAX, f32::min);
+                    assert!((distance - frame.radius).abs() < 1e-5, "p={}", p);
+                }
+
+                #[cfg(feature = "uvf")]
+                if let Some(VertexAttributeValues::Uint32(faces)) = mesh.attribute(ATTRIBUTE_FACE) {
+                    assert!(faces.iter().all(|&face| face < 20));
+                }
+                if let Some(VertexAttributeValues::Float32x2(uvs)) =
+                    mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+                {
+                    for &uv in uvs {
+                        assert!(Vec2::from(uv).cmpge(Vec2::ZERO).all(), "uv={:?}", uv);
+                        assert!(Vec2::from(uv).cmple(Vec2::ONE).all(), "uv={:?}", uv);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+
+use crate::{build_box_topology, BoxEdges, BoxPrimitives, RoundedBoxMeshOptions};
+
+/// A frustum of a rectangular pyramid with rounded edges and corners, such as a pedestal or a
+/// tapered button.
+///
+/// The base is at -Z and the top at +Z. The faces are numbered as for
+/// [`RoundedBox`](crate::RoundedBox), and the fillets along the slanted faces are exact.
+#[derive(Copy, Clone, Debug)]
+pub struct RoundedFrustum {
+    /// The dimensions of the base.
+    pub base_size: Vec2,
+    /// The dimensions of the top.
+    pub top_size: Vec2,
+    /// The height of the frustum.
+    pub height: f32,
+    /// The radius of the corners and edges.
+    pub radius: f32,
+}
+
+impl RoundedFrustum {
+    /// Creates a rounded frustum.
+    pub const fn new(base_size: Vec2, top_size: Vec2, height: f32, radius: f32) -> Self {
+        Self {
+            base_size,
+            top_size,
+            height,
+            radius,
+        }
+    }
+}
+
+impl Default for RoundedFrustum {
+    fn default() -> Self {
+        Self::new(Vec2::ONE, Vec2::splat(0.5), 1.0, 0.1)
+    }
+}
+
+impl Meshable for RoundedFrustum {
+    type Output = RoundedFrustumMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        RoundedFrustumMeshBuilder {
+            frustum: *self,
+            subdivisions: 4,
+            options: RoundedBoxMeshOptions::DEFAULT,
+        }
+    }
+}
+
+impl From<RoundedFrustum> for Mesh {
+    fn from(value: RoundedFrustum) -> Self {
+        value.mesh().build()
+    }
+}
+
+/// A builder used for creating a [`Mesh`] with a [`RoundedFrustum`] shape.
+#[derive(Copy, Clone, Debug)]
+pub struct RoundedFrustumMeshBuilder {
+    /// The [`RoundedFrustum`] shape.
+    pub frustum: RoundedFrustum,
+    /// The number of sectors and stacks in each corner.
+    pub subdivisions: usize,
+    /// Mesh generation options.
+    pub options: RoundedBoxMeshOptions,
+}
+
+impl Default for RoundedFrustumMeshBuilder {
+    fn default() -> Self {
+        RoundedFrustum::default().mesh()
+    }
+}
+
+/// Interpolates between two unit vectors along the great circle through them.
+fn slerp(from: Vec3, to: Vec3, t: f32) -> Vec3 {
+    let angle = from.angle_between(to);
+    if angle < 1e-6 {
+        return from.lerp(to, t).normalize();
+    }
+    ((1.0 - t) * angle).sin() / angle.sin() * from + (t * angle).sin() / angle.sin() * to
+}
+
+/// The corners of a [`RoundedFrustum`], which is a frustum with its faces moved inwards by the
+/// radius, expanded by a sphere.
+struct FrustumCorners {
+    base_size: Vec2,
+    half_height: f32,
+    radius: f32,
+    /// The normals of the +X and +Y faces.
+    side_normals: [Vec3; 2],
+}
+
+impl FrustumCorners {
+    fn new(frustum: &RoundedFrustum) -> Self {
+        let slope = (frustum.base_size - frustum.top_size) / 2.0;
+        Self {
+            base_size: frustum.base_size,
+            half_height: frustum.height / 2.0,
+            radius: frustum.radius,
+            side_normals: [
+                Vec3::new(frustum.height, 0.0, slope.x).normalize(),
+                Vec3::new(0.0, frustum.height, slope.y).normalize(),
+            ],
+        }
+    }
+
+    /// Returns the corner of the inner frustum in the octant of `corner`, where its faces meet.
+    fn inner_corner(&self, corner: Vec3) -> Vec3 {
+        let z = corner.z * (self.half_height - self.radius);
+        let [x, y] = [0, 1].map(|axis| {
+            let normal = self.side_normals[axis];
+            let distance = normal[axis] * self.base_size[axis] / 2.0 - normal.z * self.half_height;
+            (distance - self.radius - normal.z * z) / normal[axis]
+        });
+        Vec3::new(x, y, z) * corner.with_z(1.0)
+    }
+
+    /// Returns the position and outward normal of the point which corresponds to `dir` on the
+    /// unit sphere, in the octant of `corner`.
+    ///
+    /// The normals sweep between the normals of the faces which meet at the corner, by the
+    /// angles of the sector and stack.
+    fn point(&self, corner: Vec3, dir: Vec3) -> (Vec3, Vec3) {
+        let unit = dir.abs();
+        let theta = unit.y.atan2(unit.x) / FRAC_PI_2;
+        let phi = unit.z.atan2(unit.xy().length()) / FRAC_PI_2;
+        let [x_face, y_face] = [0, 1].map(|axis| self.side_normals[axis] * corner.with_z(1.0));
+        let normal = slerp(slerp(x_face, y_face, theta), Vec3::Z * corner.z, phi);
+        (self.inner_corner(corner) + self.radius * normal, normal)
+    }
+}
+
+impl MeshBuilder for RoundedFrustumMeshBuilder {
+    fn build(&self) -> Mesh {
+        build_frustum(&self.frustum, self.subdivisions, UVec3::ONE, &self.options)
+    }
+}
+
+/// Builds a [`RoundedFrustum`] with the topology of a box, whose flat regions are divided into
+/// `segments` along each axis.
+pub(crate) fn build_frustum(
+    frustum: &RoundedFrustum,
+    subdivisions: usize,
+    segments: UVec3,
+    options: &RoundedBoxMeshOptions,
+) -> Mesh {
+    let RoundedFrustum {
+        base_size,
+        top_size,
+        height,
+        radius,
+    } = *frustum;
+    debug_assert!(subdivisions > 0);
+    debug_assert!(2.0 * radius <= height);
+    let corners = FrustumCorners::new(frustum);
+
+    // Texture coordinates of the top, bottom and sides are laid out as for boxes of their
+    // sizes, with the sides as high as the length of their slopes
+    let slant = ((base_size - top_size) / 2.0).map(|run| run.hypot(height));
+    let uv_edges = [
+        (top_size, height),
+        (base_size, height),
+        ((top_size + base_size) / 2.0, slant.x),
+        ((top_size + base_size) / 2.0, slant.y),
+    ]
+    .map(|(size, height)| BoxEdges {
+        half_size: (size / 2.0).extend(height / 2.0),
+        radii: [[Vec3::splat(radius); 4]; 3],
+    });
+
+    build_box_topology(
+        subdivisions,
+        subdivisions,
+        segments,
+        options,
+        false,
+        BoxPrimitives::Triangles,
+        |corner, dir| corners.point(corner, dir),
+        |face| match face {
+            0 => &uv_edges[0],
+            5 => &uv_edges[1],
+            2 | 4 => &uv_edges[2],
+            _ => &uv_edges[3],
+        },
+    )
+}
+
+impl RoundedFrustumMeshBuilder {
+    /// Sets the number of subdivisions.
+    pub const fn with_subdivisions(self, subdivisions: usize) -> Self {
+        RoundedFrustumMeshBuilder {
+            subdivisions,
+            ..self
+        }
+    }
+
+    /// Sets the mesh generation options.
+    pub const fn with_options(self, options: RoundedBoxMeshOptions) -> Self {
+        RoundedFrustumMeshBuilder { options, ..self }
+    }
+
+    /// Enable generating [`Mesh::ATTRIBUTE_UV_0`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub const fn with_uv(mut self) -> Self {
+        self.options = self.options.with_uv();
+        self
+    }
+
+    /// Enable generating [`ATTRIBUTE_FACE`](crate::ATTRIBUTE_FACE). Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub const fn with_face(mut self) -> Self {
+        self.options = self.options.with_face();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "uvf")]
+    use crate::ATTRIBUTE_FACE;
+    use crate::{test_helpers::*, RoundedBox};
+    #[cfg(feature = "uvf")]
+    use bevy::render::mesh::VertexAttributeValues;
+
+    #[test]
+    fn test_rounded_frustum() {
+        let frustum = RoundedFrustum::new(Vec2::new(1.0, 0.8), Vec2::new(0.4, 0.6), 1.0, 0.1);
+        for subdivisions in 1..=6 {
+            for options in MESH_OPTIONS {
+                println!("subdivisions={} options={:?}", subdivisions, options);
+                let mesh = frustum
+                    .mesh()
+                    .with_subdivisions(subdivisions)
+                    .with_options(options)
+                    .build();
+                assert_no_degenerates(&mesh);
+                assert_no_duplicates(&mesh);
+                assert_unit_normals(&mesh);
+                assert_outward_normals(&mesh);
+                assert_closed(&mesh);
+                #[cfg(feature = "uvf")]
+                if let Some(VertexAttributeValues::Uint32(faces)) = mesh.attribute(ATTRIBUTE_FACE) {
+                    assert!(faces.iter().all(|&face| face < 6));
+                }
+
+                let pos = vertex_positions(&mesh);
+
+                // The flat faces touch the planes of the unrounded frustum
+                for (normal, distance) in [
+                    (Vec3::new(1.0, 0.0, 0.3), 0.35),
+                    (Vec3::new(-1.0, 0.0, 0.3), 0.35),
+                    (Vec3::new(0.0, 1.0, 0.1), 0.35),
+                    (Vec3::new(0.0, -1.0, 0.1), 0.35),
+                    (Vec3::Z, 0.5),
+                    (Vec3::NEG_Z, 0.5),
+                ] {
+                    let furthest = pos
+                        .iter()
+                        .map(|&p| normal.dot(p.into()))
+                        .fold(f32::NEG_INFINITY, f32::max);
+                    assert!((furthest - distance).abs() < 1e-5, "normal={}", normal);
+                }
+
+                // Each vertex is the furthest point of the convex surface along its normal
+                let normals = vertex_normals(&mesh);
+                for (&p, &normal) in pos.iter().zip(normals) {
+                    let normal = Vec3::from(normal);
+                    let support = normal.dot(p.into());
+                    for &q in pos {
+                        assert!(normal.dot(q.into()) <= support + 1e-5, "p={:?}", p);
+                    }
+                }
+            }
+        }
+
+        // Without a taper, the frustum is a box
+        let frustum_mesh = RoundedFrustum::new(Vec2::new(1.0, 0.5), Vec2::new(1.0, 0.5), 0.8, 0.2)
+            .mesh()
+            .build();
+        let box_mesh = RoundedBox::new(Vec3::new(1.0, 0.5, 0.8), 0.2)
+            .mesh()
+            .build();
+        for attribute in [Mesh::ATTRIBUTE_POSITION, Mesh::ATTRIBUTE_NORMAL] {
+            let frustum_values = frustum_mesh.attribute(attribute).unwrap().as_float3();
+            let box_values = box_mesh.attribute(attribute).unwrap().as_float3();
+            for (&a, &b) in frustum_values.unwrap().iter().zip(box_values.unwrap()) {
+                assert!(
+                    Vec3::from(a).abs_diff_eq(b.into(), 1e-5),
+                    "a={:?} b={:?}",
+                    a,
+                    b
+                );
+            }
+        }
+
+        // The texture coordinates of the sides run along their slopes, as for a box as high
+        // as the slopes are long
+        #[cfg(feature = "uvf")]
+        {
+            let frustum_mesh = frustum.mesh().with_uv().with_face().build();
+            for (faces, height) in [([2, 4], 0.3f32.hypot(1.0)), ([1, 3], 0.1f32.hypot(1.0))] {
+                let box_mesh = RoundedBox::new(Vec3::new(0.7, 0.7, height), 0.1)
+                    .mesh()
+                    .with_uv()
+                    .with_face()
+                    .build();
+                let uvs = |mesh: &Mesh| match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+                    Some(VertexAttributeValues::Float32x2(uvs)) => uvs.clone(),
+                    _ => panic!("missing uvs"),
+                };
+                let Some(VertexAttributeValues::Uint32(face_ids)) =
+                    frustum_mesh.attribute(ATTRIBUTE_FACE)
+                else {
+                    panic!("missing faces");
+                };
+                for ((&face, a), b) in face_ids.iter().zip(uvs(&frustum_mesh)).zip(uvs(&box_mesh)) {
+                    if faces.contains(&face) {
+                        assert!(Vec2::from(a).abs_diff_eq(b.into(), 1e-5), "face={}", face);
+                    }
+                }
+            }
+        }
+    }
+}
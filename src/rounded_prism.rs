@@ -0,0 +1,251 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use crate::{
+    extrude::{edge_rings, fillet_columns, Caps, Extrusion},
+    RoundedBoxMeshOptions,
+};
+
+/// A regular prism with rounded edges and corners, such as a hexagonal tile or a crystal.
+///
+/// The axis of the prism is parallel to Z, and one of its sides faces +X. For
+/// [`ATTRIBUTE_FACE`](crate::ATTRIBUTE_FACE), the top is numbered 0 and the bottom n + 1. The
+/// sides are numbered 1 to n anticlockwise around Z, so that a prism with four sides is numbered
+/// like a [`RoundedBox`](crate::RoundedBox), with the side facing +X last.
+#[derive(Copy, Clone, Debug)]
+pub struct RoundedPrism {
+    /// The number of sides, which must be at least 3.
+    pub sides: u32,
+    /// The distance from the axis to the corners of the unrounded prism.
+    pub circumradius: f32,
+    /// The height of the prism.
+    pub height: f32,
+    /// The radius of the corners and edges, which must not exceed the distance from the axis
+    /// to the sides or half the height.
+    pub radius: f32,
+}
+
+impl RoundedPrism {
+    /// Creates a rounded prism.
+    pub const fn new(sides: u32, circumradius: f32, height: f32, radius: f32) -> Self {
+        Self {
+            sides,
+            circumradius,
+            height,
+            radius,
+        }
+    }
+
+    /// Returns the distance from the axis to the sides.
+    pub fn apothem(&self) -> f32 {
+        self.circumradius * (PI / self.sides as f32).cos()
+    }
+}
+
+impl Default for RoundedPrism {
+    fn default() -> Self {
+        Self::new(6, 0.5, 1.0, 0.1)
+    }
+}
+
+impl Meshable for RoundedPrism {
+    type Output = RoundedPrismMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        RoundedPrismMeshBuilder {
+            prism: *self,
+            subdivisions: 4,
+            options: RoundedBoxMeshOptions::DEFAULT,
+        }
+    }
+}
+
+impl From<RoundedPrism> for Mesh {
+    fn from(value: RoundedPrism) -> Self {
+        value.mesh().build()
+    }
+}
+
+/// A builder used for creating a [`Mesh`] with a [`RoundedPrism`] shape.
+#[derive(Copy, Clone, Debug)]
+pub struct RoundedPrismMeshBuilder {
+    /// The [`RoundedPrism`] shape.
+    pub prism: RoundedPrism,
+    /// The number of segments in each corner, and in each rounded edge of the top or bottom.
+    pub subdivisions: usize,
+    /// Mesh generation options.
+    pub options: RoundedBoxMeshOptions,
+}
+
+impl Default for RoundedPrismMeshBuilder {
+    fn default() -> Self {
+        RoundedPrism::default().mesh()
+    }
+}
+
+impl MeshBuilder for RoundedPrismMeshBuilder {
+    fn build(&self) -> Mesh {
+        debug_assert!(self.subdivisions > 0);
+        let split_faces = self.options.is_split_faces();
+        let subdivisions = if split_faces {
+            self.subdivisions + self.subdivisions % 2
+        } else {
+            self.subdivisions
+        } as u32;
+        let RoundedPrism {
+            sides,
+            circumradius,
+            height,
+            radius,
+        } = self.prism;
+        debug_assert!(sides >= 3);
+        let apothem = self.prism.apothem();
+        debug_assert!(radius <= apothem && 2.0 * radius <= height);
+
+        // The corners anticlockwise from the one after the side facing +X, so that the side
+        // which leaves the first corner is numbered 1
+        let half_angle = PI / sides as f32;
+        let outline: Vec<Vec2> = (0..sides)
+            .map(|corner| circumradius * Vec2::from_angle((2 * corner + 1) as f32 * half_angle))
+            .collect();
+        let core_height = height / 2.0 - radius;
+        let (rings, side_length) = edge_rings(
+            subdivisions,
+            radius,
+            core_height,
+            [0, sides + 1],
+            split_faces,
+        );
+        // Place the corners of the inner prism exactly, so that fully rounded edges meet at the
+        // axis
+        let inner_circumradius = (apothem - radius) / half_angle.cos();
+        let mut columns = fillet_columns(&outline, radius, radius, subdivisions, split_faces);
+        for column in &mut columns {
+            column.corner = inner_circumradius * column.corner.normalize_or_zero();
+        }
+        let extrusion = Extrusion {
+            rings,
+            side_length,
+            columns,
+            caps: Caps::Fan,
+            curved_sides: false,
+        };
+        extrusion.build(&self.options, |pos, _| 0.5 + 0.5 * pos / circumradius)
+    }
+}
+
+impl RoundedPrismMeshBuilder {
+    /// Sets the number of subdivisions.
+    pub const fn with_subdivisions(self, subdivisions: usize) -> Self {
+        RoundedPrismMeshBuilder {
+            subdivisions,
+            ..self
+        }
+    }
+
+    /// Sets the mesh generation options.
+    pub const fn with_options(self, options: RoundedBoxMeshOptions) -> Self {
+        RoundedPrismMeshBuilder { options, ..self }
+    }
+
+    /// Enable generating [`Mesh::ATTRIBUTE_UV_0`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub const fn with_uv(mut self) -> Self {
+        self.options = self.options.with_uv();
+        self
+    }
+
+    /// Enable generating [`ATTRIBUTE_FACE`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub const fn with_face(mut self) -> Self {
+        self.options = self.options.with_face();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    #[cfg(feature = "uvf")]
+    use crate::ATTRIBUTE_FACE;
+    use bevy::render::mesh::VertexAttributeValues;
+    use std::f32::consts::TAU;
+
+    #[test]
+    fn test_rounded_prism() {
+        for subdivisions in 1..=6 {
+            for options in MESH_OPTIONS {
+                println!("subdivisions={} options={:?}", subdivisions, options);
+                for sides in [3, 4, 6] {
+                    let apothem = RoundedPrism::new(sides, 0.5, 1.0, 0.0).apothem();
+                    // Rounded, sharp and fully rounded edges
+                    for radius in [0.1, 0.0, apothem] {
+                        let prism = RoundedPrism::new(sides, 0.5, 1.0, radius);
+                        let mesh = prism
+                            .mesh()
+                            .with_subdivisions(subdivisions)
+                            .with_options(options)
+                            .build();
+                        assert_no_degenerates(&mesh);
+                        assert_no_duplicates(&mesh);
+                        assert_unit_normals(&mesh);
+                        assert_outward_normals(&mesh);
+                        assert_closed(&mesh);
+                        if sides == 4 {
+                            assert_bounds(&mesh, Vec2::splat(2.0 * apothem).extend(1.0));
+                        }
+
+                        // The sides touch the sides of the unrounded prism
+                        let pos = vertex_positions(&mesh);
+                        for side in 0..sides {
+                            let normal = Vec2::from_angle(side as f32 * TAU / sides as f32);
+                            let furthest = pos
+                                .iter()
+                                .map(|&p| normal.dot(Vec3::from(p).xy()))
+                                .fold(f32::NEG_INFINITY, f32::max);
+                            assert!((furthest - apothem).abs() < 1e-5, "side={}", side);
+                        }
+
+                        #[cfg(feature = "uvf")]
+                        if let Some(VertexAttributeValues::Uint32(faces)) =
+                            mesh.attribute(ATTRIBUTE_FACE)
+                        {
+                            assert!(faces.iter().all(|&face| face < sides + 2));
+                        }
+                        if let Some(VertexAttributeValues::Float32x2(uvs)) =
+                            mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+                        {
+                            for &uv in uvs {
+                                let uv = Vec2::from(uv);
+                                assert!(uv.cmpge(Vec2::splat(-1e-5)).all(), "uv={}", uv);
+                                assert!(uv.cmple(Vec2::splat(1.0 + 1e-5)).all(), "uv={}", uv);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Faces numbered beyond those of a box can be omitted too
+        let prism = RoundedPrism::new(6, 0.5, 1.0, 0.1);
+        for options in MESH_OPTIONS {
+            let mesh = prism.mesh().with_options(options).build();
+            let open = prism.mesh().with_options(options.without_face(7)).build();
+            assert_no_degenerates(&open);
+            assert_outward_normals(&open);
+            let pos = vertex_positions(&open);
+            let bottom = open
+                .indices()
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>()
+                .chunks_exact(3)
+                .filter(|triangle| triangle.iter().all(|&i| pos[i][2] == -0.5))
+                .count();
+            assert_eq!(bottom, 0);
+            assert!(open.indices().unwrap().len() < mesh.indices().unwrap().len());
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+    },
+};
+
+use crate::{right_angle_unit, BoxEdges, CornerProfile, RoundedBoxMeshOptions, XYQuarter};
+
+/// A rectangle with rounded corners.
+#[derive(Copy, Clone, Debug)]
+pub struct RoundedRect {
+    /// The dimensions of the rectangle.
+    pub size: Vec2,
+    /// The radius of the corners.
+    pub radius: f32,
+}
+
+impl RoundedRect {
+    /// Creates a rounded rectangle.
+    pub const fn new(size: Vec2, radius: f32) -> Self {
+        Self { size, radius }
+    }
+}
+
+impl Default for RoundedRect {
+    fn default() -> Self {
+        Self::new(Vec2::ONE, 0.1)
+    }
+}
+
+impl Meshable for RoundedRect {
+    type Output = RoundedRectMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        RoundedRectMeshBuilder {
+            rounded_rect: *self,
+            subdivisions: 4,
+            corner_radii: None,
+            options: RoundedBoxMeshOptions::DEFAULT,
+        }
+    }
+}
+
+impl From<RoundedRect> for Mesh {
+    fn from(value: RoundedRect) -> Self {
+        value.mesh().build()
+    }
+}
+
+/// A builder used for creating a [`Mesh`] with a [`RoundedRect`] shape.
+#[derive(Copy, Clone, Debug)]
+pub struct RoundedRectMeshBuilder {
+    /// The [`RoundedRect`] shape.
+    pub rounded_rect: RoundedRect,
+    /// The number of sectors in each corner.
+    pub subdivisions: usize,
+    /// The radius of each corner, overriding [`RoundedRect::radius`] if set.
+    ///
+    /// The corners are ordered anticlockwise, starting from where both coordinates are
+    /// positive. A radius of zero gives a sharp corner.
+    pub corner_radii: Option<[f32; 4]>,
+    /// Mesh generation options.
+    ///
+    /// The rectangle is a single face, so omitting faces and generating
+    /// [`ATTRIBUTE_FACE`](crate::ATTRIBUTE_FACE) have no effect.
+    pub options: RoundedBoxMeshOptions,
+}
+
+impl Default for RoundedRectMeshBuilder {
+    fn default() -> Self {
+        RoundedRect::default().mesh()
+    }
+}
+
+impl MeshBuilder for RoundedRectMeshBuilder {
+    fn build(&self) -> Mesh {
+        debug_assert!(self.subdivisions > 0);
+        let subdivisions = self.subdivisions as u32;
+        let size = self.rounded_rect.size;
+        let corner_radii = self.corner_radii.unwrap_or([self.rounded_rect.radius; 4]);
+
+        // The outline is the equator of a box whose edges parallel to Z are the corners
+        let edges = BoxEdges {
+            half_size: (size / 2.0).extend(0.0),
+            radii: [
+                [Vec3::ZERO; 4],
+                [Vec3::ZERO; 4],
+                corner_radii.map(Vec3::splat),
+            ],
+        };
+
+        // Generate vertices, starting with the centre of the fan. Corners which meet, as in a
+        // pill shape, share the vertex where they meet.
+        let mut positions: Vec<[f32; 3]> = vec![[0.0; 3]];
+        for (quarter, radius) in corner_radii.into_iter().enumerate() {
+            let corner = XYQuarter(quarter as u32).coords().extend(1.0);
+            let first_sector = quarter as u32 * subdivisions;
+            // A sharp corner needs only one vertex
+            let sectors = if radius > 0.0 { subdivisions } else { 0 };
+            for sector in first_sector..=first_sector + sectors {
+                let dir = right_angle_unit(sector as i32, subdivisions as i32).extend(0.0);
+                let (inset, _) = edges.corner_point(corner, dir, &CornerProfile::Circular);
+                let pos = ((edges.half_size - inset) * corner).to_array();
+                if positions.len() == 1 || positions.last() != Some(&pos) {
+                    positions.push(pos);
+                }
+            }
+        }
+        if positions.len() > 2 && positions.last() == positions.get(1) {
+            positions.pop();
+        }
+        let facing = if self.options.inverted { -1.0 } else { 1.0 };
+        let normals = vec![[0.0, 0.0, facing]; positions.len()];
+
+        // Generate indices
+        let reversed = self.options.is_reversed(self.options.inverted);
+        let outline = positions.len() as u32 - 1;
+        let indices: Vec<u32> = (0..outline)
+            .flat_map(|i| {
+                let [a, b] = [1 + i, 1 + (i + 1) % outline];
+                if reversed {
+                    [0, b, a]
+                } else {
+                    [0, a, b]
+                }
+            })
+            .collect();
+
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        if self.options.is_generate_uv() {
+            let uvs: Vec<[f32; 2]> = positions
+                .iter()
+                .map(|&[x, y, _]| {
+                    let uv = Vec2::new(0.5 + x / size.x, 0.5 - y / size.y);
+                    let uv = if self.options.inverted {
+                        Vec2::new(1.0 - uv.x, uv.y)
+                    } else {
+                        uv
+                    };
+                    uv.to_array()
+                })
+                .collect();
+            mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        }
+        mesh.with_inserted_indices(Indices::U32(indices))
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    }
+}
+
+impl RoundedRectMeshBuilder {
+    /// Sets the number of subdivisions.
+    pub const fn with_subdivisions(self, subdivisions: usize) -> Self {
+        RoundedRectMeshBuilder {
+            subdivisions,
+            ..self
+        }
+    }
+
+    /// Sets the radius of each corner, overriding [`RoundedRect::radius`].
+    pub const fn with_corner_radii(self, corner_radii: [f32; 4]) -> Self {
+        RoundedRectMeshBuilder {
+            corner_radii: Some(corner_radii),
+            ..self
+        }
+    }
+
+    /// Sets the mesh generation options.
+    pub const fn with_options(self, options: RoundedBoxMeshOptions) -> Self {
+        RoundedRectMeshBuilder { options, ..self }
+    }
+
+    /// Turn the mesh over, facing towards negative Z.
+    pub const fn with_inverted(mut self) -> Self {
+        self.options = self.options.with_inverted();
+        self
+    }
+
+    /// Enable generating [`Mesh::ATTRIBUTE_UV_0`]. Requires `uvf` feature.
+    #[cfg(feature = "uvf")]
+    pub const fn with_uv(mut self) -> Self {
+        self.options = self.options.with_uv();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    #[cfg(feature = "uvf")]
+    use bevy::render::mesh::VertexAttributeValues;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_rounded_rect() {
+        let rounded_rect = RoundedRect::new(Vec2::new(2.0, 1.0), 0.25);
+        for subdivisions in 1..=10 {
+            println!("subdivisions={}", subdivisions);
+            for corner_radii in [[0.25; 4], [0.1, 0.5, 0.0, 0.25]] {
+                let builder = rounded_rect
+                    .mesh()
+                    .with_subdivisions(subdivisions)
+                    .with_corner_radii(corner_radii);
+                for mesh in [builder.build(), builder.with_inverted().build()] {
+                    assert_no_degenerates(&mesh);
+                    assert_no_duplicates(&mesh);
+                    assert_bounds(&mesh, rounded_rect.size.extend(0.0));
+                    assert_unit_normals(&mesh);
+                    assert_outward_normals(&mesh);
+                    assert!(mesh.attribute(Mesh::ATTRIBUTE_UV_0).is_none());
+                }
+                #[cfg(feature = "uvf")]
+                {
+                    let mesh = builder.with_uv().build();
+                    let Some(VertexAttributeValues::Float32x2(uvs)) =
+                        mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+                    else {
+                        panic!("missing uvs");
+                    };
+                    for &uv in uvs {
+                        assert!(Vec2::from(uv).cmpge(Vec2::ZERO).all(), "uv={:?}", uv);
+                        assert!(Vec2::from(uv).cmple(Vec2::ONE).all(), "uv={:?}", uv);
+                    }
+                }
+            }
+        }
+
+        // A pill shape shares the vertices where its corners meet
+        for size in [Vec2::new(2.0, 1.0), Vec2::new(1.0, 2.0), Vec2::ONE] {
+            let mesh = RoundedRect::new(size, 0.5).mesh().build();
+            assert_no_degenerates(&mesh);
+            assert_no_duplicates(&mesh);
+            assert_bounds(&mesh, size.extend(0.0));
+            assert_outward_normals(&mesh);
+        }
+
+        // Texture coordinates are mirrored when the rectangle is turned over
+        #[cfg(feature = "uvf")]
+        {
+            let builder = rounded_rect.mesh().with_uv();
+            let [front, back] = [builder.build(), builder.with_inverted().build()].map(|mesh| {
+                let Some(VertexAttributeValues::Float32x2(uvs)) =
+                    mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+                else {
+                    panic!("missing uvs");
+                };
+                uvs.clone()
+            });
+            for (front, back) in front.iter().zip(&back) {
+                assert_eq!([1.0 - front[0], front[1]], *back);
+            }
+        }
+
+        // Area of the rectangle less the area cut from each corner
+        let mesh = rounded_rect.mesh().with_subdivisions(64).build();
+        let pos = vertex_positions(&mesh);
+        let indices = mesh.indices().unwrap();
+        let mut it = indices.iter();
+        let mut area = 0.0;
+        while let (Some(a), Some(b), Some(c)) = (it.next(), it.next(), it.next()) {
+            let [pa, pb, pc] = [a, b, c].map(|i| Vec3::from(pos[i]));
+            area += 0.5 * (pb - pa).cross(pc - pa).z;
+        }
+        let expected = 2.0 - (4.0 - PI) * 0.25 * 0.25;
+        assert!((area - expected).abs() < 1e-3, "area={}", area);
+    }
+}
@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+use crate::RoundedBoxMeshOptions;
+
+#[cfg(feature = "uvf")]
+pub(crate) const MESH_OPTIONS: [RoundedBoxMeshOptions; 2] = [
+    RoundedBoxMeshOptions::DEFAULT,
+    RoundedBoxMeshOptions::DEFAULT.with_uv().with_face(),
+];
+#[cfg(not(feature = "uvf"))]
+pub(crate) const MESH_OPTIONS: [RoundedBoxMeshOptions; 1] = [RoundedBoxMeshOptions::DEFAULT];
+
+/// Returns the positions of the vertices of a mesh.
+pub(crate) fn vertex_positions(mesh: &Mesh) -> &[[f32; 3]] {
+    mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        .unwrap()
+        .as_float3()
+        .unwrap()
+}
+
+/// Returns the normals of the vertices of a mesh.
+pub(crate) fn vertex_normals(mesh: &Mesh) -> &[[f32; 3]] {
+    mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        .unwrap()
+        .as_float3()
+        .unwrap()
+}
+
+pub(crate) fn assert_bounds(mesh: &Mesh, size: Vec3) {
+    let pos = vertex_positions(mesh);
+    let (min, max) = pos
+        .iter()
+        .fold((Vec3::INFINITY, Vec3::NEG_INFINITY), |(min, max), &p| {
+            (min.min(p.into()), max.max(p.into()))
+        });
+    assert!(min.abs_diff_eq(-0.5 * size, 1e-5), "min={}", min);
+    assert!(max.abs_diff_eq(0.5 * size, 1e-5), "max={}", max);
+}
+
+pub(crate) fn assert_unit_normals(mesh: &Mesh) {
+    let normals = vertex_normals(mesh);
+    for &normal in normals {
+        assert!(Vec3::from(normal).is_normalized(), "normal={:?}", normal);
+    }
+}
+
+pub(crate) fn assert_outward_normals(mesh: &Mesh) {
+    let pos = vertex_positions(mesh);
+    let normals = vertex_normals(mesh);
+    let indices = mesh.indices().unwrap();
+    let mut it = indices.iter();
+    while let (Some(a), Some(b), Some(c)) = (it.next(), it.next(), it.next()) {
+        let [pa, pb, pc] = [a, b, c].map(|i| Vec3::from(pos[i]));
+        let Some(face_normal) = (pb - pa).cross(pc - pa).try_normalize() else {
+            continue;
+        };
+        for i in [a, b, c] {
+            assert!(
+                face_normal.dot(normals[i].into()) > 0.0,
+                "face_normal={} normal={:?} tri={:?}",
+                face_normal,
+                normals[i],
+                [pa, pb, pc]
+            );
+        }
+    }
+}
+
+pub(crate) fn assert_closed(mesh: &Mesh) {
+    let pos = vertex_positions(mesh);
+    // Adding zero treats -0.0 and 0.0 as the same position
+    let key = |i: usize| pos[i].map(|c| (c + 0.0).to_bits());
+    let mut edges = HashSet::new();
+    let indices = mesh.indices().unwrap();
+    let mut it = indices.iter();
+    while let (Some(a), Some(b), Some(c)) = (it.next(), it.next(), it.next()) {
+        for (from, to) in [(a, b), (b, c), (c, a)] {
+            assert!(edges.insert((key(from), key(to))), "p={:?}", pos[from]);
+        }
+    }
+    for &(from, to) in &edges {
+        assert!(edges.contains(&(to, from)), "p={:?}", from);
+    }
+}
+
+pub(crate) fn assert_no_degenerates(mesh: &Mesh) {
+    let pos = vertex_positions(mesh);
+    let indices = mesh.indices().unwrap();
+    let mut it = indices.iter();
+    while let (Some(a), Some(b), Some(c)) = (it.next(), it.next(), it.next()) {
+        assert_ne!(pos[a], pos[b]);
+        assert_ne!(pos[b], pos[c]);
+        assert_ne!(pos[c], pos[a]);
+    }
+}
+
+pub(crate) fn assert_no_duplicates(mesh: &Mesh) {
+    let pos = vertex_positions(mesh);
+    let mut set = HashSet::<[[u32; 3]; 3]>::new();
+    let indices = mesh.indices().unwrap();
+    let mut it = indices.iter();
+    while let (Some(a), Some(b), Some(c)) = (it.next(), it.next(), it.next()) {
+        let [ax, ay, az] = pos[a];
+        let [bx, by, bz] = pos[b];
+        let [cx, cy, cz] = pos[c];
+        let mut ps = [
+            [ax.to_bits(), ay.to_bits(), az.to_bits()],
+            [bx.to_bits(), by.to_bits(), bz.to_bits()],
+            [cx.to_bits(), cy.to_bits(), cz.to_bits()],
+        ];
+        assert!(set.insert(ps));
+        ps.rotate_left(1);
+        assert!(set.insert(ps));
+        ps.rotate_left(1);
+        assert!(set.insert(ps));
+    }
+}